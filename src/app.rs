@@ -1,6 +1,18 @@
-use crate::models::GroupStats;
+use crate::file_filter::{self, FileFilter, FileRules, OriginalFilter};
+use crate::keymap::Keymap;
+use crate::models::{format_bytes, format_size_exact, parse_size, CleanScope, FileInfo, GroupStats, MediaType};
 use crate::time_range::TimeRange;
+use crate::treemap::{Tile, TileTarget};
 use crate::logger::Logger;
+use crate::policy::{self, GroupPolicy};
+use crate::preflight::{self, PreflightRequest};
+use crate::preview::{self, PreviewState};
+use crate::projection::{self, Projection};
+use crate::search::fuzzy_match;
+use regex::Regex;
+use chrono::{DateTime, Datelike, NaiveDate, Utc};
+use std::cell::RefCell;
+use std::collections::{BTreeMap, HashMap, HashSet, VecDeque};
 use std::path::PathBuf;
 use std::sync::Arc;
 
@@ -9,11 +21,12 @@ pub enum AppTab {
     Analysis,
     Clean,
     Migrate,
+    Logs,
 }
 
 impl AppTab {
     pub fn titles() -> Vec<&'static str> {
-        vec!["分析", "清理", "迁移"]
+        vec![t!("tab.analysis"), t!("tab.clean"), t!("tab.migrate"), t!("tab.logs")]
     }
 
     pub fn from_index(index: usize) -> Self {
@@ -21,6 +34,7 @@ impl AppTab {
             0 => AppTab::Analysis,
             1 => AppTab::Clean,
             2 => AppTab::Migrate,
+            3 => AppTab::Logs,
             _ => AppTab::Analysis,
         }
     }
@@ -28,9 +42,82 @@ impl AppTab {
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum SortBy {
-    Size,
+    Size,         // 当前时间范围内的大小
     FileCount,
     Name,
+    LastActivity,
+    MissingCount,
+    MemberCount,
+    CreateTime,
+}
+
+impl SortBy {
+    pub fn next(self) -> Self {
+        match self {
+            SortBy::Size => SortBy::FileCount,
+            SortBy::FileCount => SortBy::Name,
+            SortBy::Name => SortBy::LastActivity,
+            SortBy::LastActivity => SortBy::MissingCount,
+            SortBy::MissingCount => SortBy::MemberCount,
+            SortBy::MemberCount => SortBy::CreateTime,
+            SortBy::CreateTime => SortBy::Size,
+        }
+    }
+
+    /// 切换到该排序时的默认方向：名称从小到大，其余从大到小
+    pub fn default_descending(&self) -> bool {
+        !matches!(self, SortBy::Name)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            SortBy::Size => t!("sort.size"),
+            SortBy::FileCount => t!("sort.file_count"),
+            SortBy::Name => t!("sort.name"),
+            SortBy::LastActivity => t!("sort.last_activity"),
+            SortBy::MissingCount => t!("sort.missing_count"),
+            SortBy::MemberCount => t!("sort.member_count"),
+            SortBy::CreateTime => t!("sort.create_time"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FileSortBy {
+    Time,
+    Size,
+    Name,
+    MediaType,
+}
+
+impl FileSortBy {
+    pub fn next(self) -> Self {
+        match self {
+            FileSortBy::Time => FileSortBy::Size,
+            FileSortBy::Size => FileSortBy::Name,
+            FileSortBy::Name => FileSortBy::MediaType,
+            FileSortBy::MediaType => FileSortBy::Time,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            FileSortBy::Time => t!("file_sort.time"),
+            FileSortBy::Size => t!("sort.size"),
+            FileSortBy::Name => t!("sort.name"),
+            FileSortBy::MediaType => t!("file_sort.media_type"),
+        }
+    }
+}
+
+/// 单个群组的文件列表（在群组列表上按回车进入）
+#[derive(Debug, Clone)]
+pub struct FileView {
+    pub group_id: String,
+    pub order: Vec<usize>,      // 排序后的 GroupStats.files 下标
+    pub selected_index: usize,
+    pub sort_by: FileSortBy,
+    pub descending: bool,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
@@ -40,12 +127,44 @@ pub enum ActivityFilter {
     Inactive(i64),
 }
 
+/// 按是否仍在群中过滤（group_info 的 quit_flag）
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum QuitFilter {
+    All,
+    Quit,   // 只显示已退出的群
+    Member, // 只显示仍在其中的群
+}
+
+impl QuitFilter {
+    pub fn next(self) -> Self {
+        match self {
+            QuitFilter::All => QuitFilter::Quit,
+            QuitFilter::Quit => QuitFilter::Member,
+            QuitFilter::Member => QuitFilter::All,
+        }
+    }
+}
+
+/// 群名过滤使用的正则表达式，按源文本比较是否相同
+#[derive(Debug, Clone)]
+pub struct NamePattern(pub Regex);
+
+impl PartialEq for NamePattern {
+    fn eq(&self, other: &Self) -> bool {
+        self.0.as_str() == other.0.as_str()
+    }
+}
+
 #[derive(Debug, Clone, PartialEq)]
 pub struct GroupFilter {
     pub min_size: u64,
     pub min_file_count: usize,
     pub hide_empty: bool,
     pub activity: ActivityFilter,
+    pub name_pattern: Option<NamePattern>, // 匹配群名、备注或群号
+    pub quit: QuitFilter,
+    pub media_type: Option<MediaType>,     // 只显示包含该类型现存文件的群
+    pub files: FileRules,                  // 逐文件条件，决定清理和迁移哪些文件，不影响群是否显示
 }
 
 impl Default for GroupFilter {
@@ -55,10 +174,149 @@ impl Default for GroupFilter {
             min_file_count: 0,
             hide_empty: true,
             activity: ActivityFilter::All,
+            name_pattern: None,
+            quit: QuitFilter::All,
+            media_type: None,
+            files: FileRules::default(),
+        }
+    }
+}
+
+impl GroupFilter {
+    pub fn matches(&self, stat: &GroupStats, now: i64) -> bool {
+        if self.hide_empty && stat.exist_count == 0 {
+            return false;
+        }
+
+        if stat.total_size < self.min_size {
+            return false;
+        }
+
+        if stat.file_count < self.min_file_count {
+            return false;
+        }
+
+        match self.activity {
+            ActivityFilter::All => {}
+            ActivityFilter::Active(days) => {
+                let cutoff = now - (days * 86400);
+                let latest_time = stat.files.iter()
+                    .map(|f| f.msg_time)
+                    .max()
+                    .unwrap_or(0);
+
+                if latest_time < cutoff {
+                    return false;
+                }
+            }
+            ActivityFilter::Inactive(days) => {
+                let cutoff = now - (days * 86400);
+                let latest_time = stat.files.iter()
+                    .map(|f| f.msg_time)
+                    .max()
+                    .unwrap_or(0);
+
+                if latest_time >= cutoff {
+                    return false;
+                }
+            }
+        }
+
+        if let Some(NamePattern(ref regex)) = self.name_pattern {
+            let matched = [Some(stat.group_name.as_str()), stat.group_remark(), Some(stat.group_id.as_str())]
+                .into_iter()
+                .flatten()
+                .any(|text| regex.is_match(text));
+            if !matched {
+                return false;
+            }
+        }
+
+        match self.quit {
+            QuitFilter::All => {}
+            QuitFilter::Quit if !stat.has_quit() => return false,
+            QuitFilter::Member if stat.has_quit() => return false,
+            _ => {}
+        }
+
+        if let Some(media_type) = self.media_type {
+            let has_type = stat.files.iter()
+                .any(|f| f.actual_size.is_some() && f.media_type() == media_type);
+            if !has_type {
+                return false;
+            }
+        }
+
+        true
+    }
+}
+
+/// 过滤器对话框中的选项，按显示顺序排列
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum FilterOption {
+    HideEmpty,
+    Activity,
+    MinSize,
+    MinFileCount,
+    NamePattern,
+    Quit,
+    MediaType,
+    FileMinSize,
+    FileMaxSize,
+    Extensions,
+    Animation,
+}
+
+impl FilterOption {
+    pub const ALL: [FilterOption; 11] = [
+        FilterOption::HideEmpty,
+        FilterOption::Activity,
+        FilterOption::MinSize,
+        FilterOption::MinFileCount,
+        FilterOption::NamePattern,
+        FilterOption::Quit,
+        FilterOption::MediaType,
+        FilterOption::FileMinSize,
+        FilterOption::FileMaxSize,
+        FilterOption::Extensions,
+        FilterOption::Animation,
+    ];
+}
+
+
+/// 迁移完成后如何处理原文件
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum MigrateMode {
+    Copy,   // 保留原文件
+    Move,   // 删除原文件
+    Link,   // 原文件替换为指向迁移副本的符号链接
+}
+
+impl MigrateMode {
+    pub fn next(self) -> Self {
+        match self {
+            MigrateMode::Copy => MigrateMode::Move,
+            MigrateMode::Move => MigrateMode::Link,
+            MigrateMode::Link => MigrateMode::Copy,
         }
     }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            MigrateMode::Copy => t!("migrate_mode.copy"),
+            MigrateMode::Move => t!("migrate_mode.move"),
+            MigrateMode::Link => t!("migrate_mode.link"),
+        }
+    }
+
+    /// 是否会改动 QQ 目录中的原文件
+    pub fn modifies_source(&self) -> bool {
+        !matches!(self, MigrateMode::Copy)
+    }
 }
 
+/// 界面中保留的日志条数，更早的只保存在日志文件中
+const LOG_BUFFER_SIZE: usize = 500;
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum LogLevel {
@@ -68,6 +326,104 @@ pub enum LogLevel {
     Error,
 }
 
+impl LogLevel {
+    pub fn label(&self) -> &'static str {
+        match self {
+            LogLevel::Info => "INFO",
+            LogLevel::Success => "OK",
+            LogLevel::Warning => "WARN",
+            LogLevel::Error => "ERR",
+        }
+    }
+}
+
+#[derive(Debug, Clone)]
+pub struct LogEntry {
+    pub time: String,
+    pub level: LogLevel,
+    pub message: String,
+}
+
+/// 日志页的级别过滤
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum LogFilter {
+    All,
+    WarningsAndErrors,
+    ErrorsOnly,
+}
+
+impl LogFilter {
+    pub fn next(self) -> Self {
+        match self {
+            LogFilter::All => LogFilter::WarningsAndErrors,
+            LogFilter::WarningsAndErrors => LogFilter::ErrorsOnly,
+            LogFilter::ErrorsOnly => LogFilter::All,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            LogFilter::All => t!("log_filter.all"),
+            LogFilter::WarningsAndErrors => t!("log_filter.warnings"),
+            LogFilter::ErrorsOnly => t!("log_filter.errors"),
+        }
+    }
+
+    pub fn matches(&self, level: LogLevel) -> bool {
+        match self {
+            LogFilter::All => true,
+            LogFilter::WarningsAndErrors => matches!(level, LogLevel::Warning | LogLevel::Error),
+            LogFilter::ErrorsOnly => level == LogLevel::Error,
+        }
+    }
+}
+
+/// 分析页时间分布图的统计口径
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartMetric {
+    Bytes,
+    Count,
+}
+
+impl ChartMetric {
+    pub fn next(self) -> Self {
+        match self {
+            ChartMetric::Bytes => ChartMetric::Count,
+            ChartMetric::Count => ChartMetric::Bytes,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            ChartMetric::Bytes => t!("chart.bytes"),
+            ChartMetric::Count => t!("chart.count"),
+        }
+    }
+}
+
+/// 分析页时间分布的显示方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ChartView {
+    Monthly, // 按月柱状图
+    Heatmap, // 按周 × 星期的日历热力图
+}
+
+/// 一段时间内的媒体活动
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Activity {
+    pub bytes: u64,
+    pub count: usize,
+}
+
+impl Activity {
+    pub fn value(&self, metric: ChartMetric) -> u64 {
+        match metric {
+            ChartMetric::Bytes => self.bytes,
+            ChartMetric::Count => self.count as u64,
+        }
+    }
+}
+
 #[derive(Debug, Clone)]
 pub struct OperationProgress {
     pub total: usize,
@@ -87,6 +443,33 @@ impl Default for OperationProgress {
     }
 }
 
+/// 决定统计缓存是否有效的条件
+#[derive(PartialEq)]
+struct ViewKey {
+    time_range: TimeRange,
+    original_filter: OriginalFilter,
+    rules: FileRules,
+    group_policy: GroupPolicy,
+    clean_view: bool, // 清理页面的统计包含清理策略
+    revision: u64,    // 群组数据或选择的版本
+}
+
+/// 群组在当前条件下的文件统计
+#[derive(Clone, Copy)]
+struct RangeCounts {
+    size: u64,
+    exist_count: usize,
+    file_count: usize,
+}
+
+/// 每帧都会用到的统计结果，条件、策略或选择变化后重新计算
+#[derive(Default)]
+struct ViewCache {
+    key: Option<ViewKey>,
+    counts: HashMap<String, RangeCounts>,  // 群号 -> 统计
+    projections: [Option<Projection>; 2],  // 只算已选群 / 未选择时按全部群
+}
+
 pub struct App {
     pub should_quit: bool,
     pub current_tab: AppTab,
@@ -94,9 +477,25 @@ pub struct App {
     pub filtered_stats: Vec<usize>,
     pub selected_index: usize,
     pub selected_groups: Vec<bool>,
+    pub selected_files: HashMap<String, HashSet<String>>,  // 群号 -> 手动选择的文件
+    pub file_view: Option<FileView>,
+    pub preview_enabled: bool,
+    pub details_enabled: bool,  // 群组列表右侧显示当前群的详情
+    pub preview: PreviewState,
     pub sort_by: SortBy,
+    pub sort_descending: bool,
     pub filter: GroupFilter,
+    pub search_query: String,
+    pub search_active: bool,    // 正在输入搜索内容
     pub time_range: TimeRange,
+    pub time_input: Option<String>,       // 正在输入的时间范围，None 表示未在输入
+    pub time_input_error: Option<String>, // 上次输入无法解析的原因
+    pub clean_scope: CleanScope,
+    pub original_filter: OriginalFilter,
+    pub group_policy: GroupPolicy,
+    pub pinned_groups: HashSet<String>,     // 全局预算淘汰时跳过的群
+    pub budget_input: Option<String>,       // 正在输入的全局存储预算
+    pub budget_input_error: Option<String>, // 上次输入无法解析的原因
     pub progress: OperationProgress,
     pub migrate_target_path: PathBuf,
     pub migrate_presets: Vec<PathBuf>,
@@ -105,20 +504,39 @@ pub struct App {
     pub show_filter_dialog: bool,
     pub show_confirm_dialog: bool,
     pub confirm_action: Option<ConfirmAction>,
-    pub temp_migrate_keep_original: bool,
+    pub temp_migrate_mode: MigrateMode,
+    pub preflight_issues: Vec<String>,
+    pub qq_data_dir: PathBuf,
+    pub doctor_requested: bool,
     pub temp_filter: GroupFilter,
     pub filter_cursor: usize,
+    pub filter_input: Option<String>,       // 过滤器中正在编辑的文字输入
+    pub filter_input_error: Option<String>, // 上次输入无法解析的原因
     pub logger: Arc<Logger>,
+    pub keymap: Keymap,
+    pub logs: VecDeque<LogEntry>,
+    pub log_filter: LogFilter,
+    pub log_scroll: usize,      // 距离最新一条的行数，0 表示跟随最新日志
+    pub chart_metric: ChartMetric,
+    pub chart_view: ChartView,
+    pub chart_selected_only: bool, // 时间分布只统计已选择的群
+    pub chart_scroll: usize,       // 距离最新一列的月数或周数，0 表示显示到最新
+    pub treemap_group: Option<String>, // 树图中进入的群，None 为全部群
+    pub treemap_year: Option<i32>,     // 进入群后再进入的年份
+    pub treemap_cursor: usize,
+    view_cache: RefCell<ViewCache>,
+    view_revision: u64,
 }
 
 #[derive(Debug, Clone, Copy, PartialEq)]
 pub enum ConfirmAction {
     Clean,
     Migrate,
+    Restore,
 }
 
 impl App {
-    pub fn new(stats: Vec<GroupStats>, logger: Arc<Logger>) -> Self {
+    pub fn new(stats: Vec<GroupStats>, logger: Arc<Logger>, qq_data_dir: PathBuf, keymap: Keymap) -> Self {
         let len = stats.len();
         let filtered_stats: Vec<usize> = (0..len).collect();
         let selected_groups = vec![false; len];
@@ -148,9 +566,25 @@ impl App {
             filtered_stats,
             selected_index: 0,
             selected_groups,
+            selected_files: HashMap::new(),
+            file_view: None,
+            preview_enabled: true,
+            details_enabled: true,
+            preview: PreviewState::new(),
             sort_by: SortBy::Size,
+            sort_descending: true,
             filter: GroupFilter::default(),
+            search_query: String::new(),
+            search_active: false,
             time_range: TimeRange::All,
+            time_input: None,
+            clean_scope: CleanScope::Both,
+            original_filter: OriginalFilter::All,
+            group_policy: GroupPolicy::None,
+            pinned_groups: HashSet::new(),
+            budget_input: None,
+            budget_input_error: None,
+            time_input_error: None,
             progress: OperationProgress::default(),
             migrate_target_path: migrate_presets[0].clone(),
             migrate_presets,
@@ -159,14 +593,32 @@ impl App {
             show_filter_dialog: false,
             show_confirm_dialog: false,
             confirm_action: None,
-            temp_migrate_keep_original: true,
+            temp_migrate_mode: MigrateMode::Copy,
+            preflight_issues: Vec::new(),
+            qq_data_dir,
+            doctor_requested: false,
             temp_filter: GroupFilter::default(),
             filter_cursor: 0,
+            filter_input: None,
+            filter_input_error: None,
             logger,
+            keymap,
+            logs: VecDeque::with_capacity(LOG_BUFFER_SIZE),
+            log_filter: LogFilter::All,
+            log_scroll: 0,
+            chart_metric: ChartMetric::Bytes,
+            chart_view: ChartView::Monthly,
+            chart_selected_only: false,
+            chart_scroll: 0,
+            treemap_group: None,
+            treemap_year: None,
+            treemap_cursor: 0,
+            view_cache: RefCell::default(),
+            view_revision: 0,
         };
 
         app.apply_filter();
-        app.add_log(LogLevel::Info, "应用启动成功");
+        app.add_log(LogLevel::Info, t!("log.app_started"));
         app
     }
 
@@ -174,10 +626,21 @@ impl App {
         self.should_quit = true;
     }
 
+    /// 切换标签页
+    ///
+    /// 保留策略只在清理页计入范围内的大小，进出清理页时按新的大小重新排序和筛选。
+    pub fn set_tab(&mut self, tab: AppTab) {
+        let was_clean = self.current_tab == AppTab::Clean;
+        self.current_tab = tab;
+        if was_clean != (tab == AppTab::Clean) && self.group_policy != GroupPolicy::None {
+            self.apply_sort();
+        }
+    }
+
     pub fn next_tab(&mut self) {
         let current_idx = self.current_tab as usize;
         let next_idx = (current_idx + 1) % AppTab::titles().len();
-        self.current_tab = AppTab::from_index(next_idx);
+        self.set_tab(AppTab::from_index(next_idx));
     }
 
     pub fn prev_tab(&mut self) {
@@ -187,7 +650,7 @@ impl App {
         } else {
             current_idx - 1
         };
-        self.current_tab = AppTab::from_index(prev_idx);
+        self.set_tab(AppTab::from_index(prev_idx));
     }
 
     pub fn next_item(&mut self) {
@@ -210,6 +673,10 @@ impl App {
         if let Some(&actual_idx) = self.filtered_stats.get(self.selected_index) {
             if actual_idx < self.selected_groups.len() {
                 self.selected_groups[actual_idx] = !self.selected_groups[actual_idx];
+                // 整组选择会覆盖之前逐个选择的文件
+                let group_id = self.stats[actual_idx].group_id.clone();
+                self.selected_files.remove(&group_id);
+                self.invalidate_view();
             }
         }
     }
@@ -218,159 +685,1004 @@ impl App {
         for &idx in &self.filtered_stats {
             if idx < self.selected_groups.len() {
                 self.selected_groups[idx] = true;
+                self.selected_files.remove(&self.stats[idx].group_id);
             }
         }
-        self.add_log(LogLevel::Info, &format!("已选择 {} 个群组", self.filtered_stats.len()));
+        self.invalidate_view();
+        self.add_log(LogLevel::Info, &t!("log.selected_groups", self.filtered_stats.len()));
     }
 
-    pub fn deselect_all(&mut self) {
-        self.selected_groups.fill(false);
-        self.add_log(LogLevel::Info, "已取消所有选择");
+    /// 选择所有已退出的群，不受当前过滤和搜索的限制
+    pub fn select_quit_groups(&mut self) {
+        let mut count = 0;
+        let mut size = 0;
+        for (idx, stat) in self.stats.iter().enumerate() {
+            if stat.has_quit() && stat.exist_count > 0 {
+                self.selected_groups[idx] = true;
+                self.selected_files.remove(&stat.group_id);
+                count += 1;
+                size += stat.total_size;
+            }
+        }
+        self.invalidate_view();
+
+        if count > 0 {
+            let message = t!("log.selected_quit_groups", count, format_bytes(size));
+            self.add_log(LogLevel::Info, &message);
+        } else {
+            self.add_log(LogLevel::Warning, t!("log.no_quit_groups"));
+        }
     }
 
-    pub fn apply_sort(&mut self) {
-        match self.sort_by {
-            SortBy::Size => {
-                self.stats.sort_by(|a, b| b.total_size.cmp(&a.total_size));
-            }
-            SortBy::FileCount => {
-                // 使用存在的文件数量排序，而非总文件数
-                self.stats.sort_by(|a, b| b.exist_count.cmp(&a.exist_count));
-            }
-            SortBy::Name => {
-                self.stats.sort_by(|a, b| a.group_name.cmp(&b.group_name));
+    pub fn toggle_pin(&mut self) {
+        let Some(stat) = self.current_group() else {
+            return;
+        };
+        let (group_id, group_name) = (stat.group_id.clone(), stat.group_name.clone());
+        let message = if self.pinned_groups.remove(&group_id) {
+            t!("log.unpinned", group_name)
+        } else {
+            self.pinned_groups.insert(group_id);
+            t!("log.pinned", group_name)
+        };
+        self.add_log(LogLevel::Info, &message);
+    }
+
+    /// 按全局存储预算选择要淘汰的文件，替换当前选择
+    pub fn select_for_budget(&mut self, budget: u64) {
+        let plan = policy::evict_for_budget(
+            &self.stats,
+            budget,
+            &self.pinned_groups,
+            &self.file_filter(),
+            self.clean_scope,
+        );
+
+        if plan.usage <= budget {
+            let message = t!("log.budget_met", format_bytes(plan.usage), format_bytes(budget));
+            self.add_log(LogLevel::Info, &message);
+            return;
+        }
+
+        self.clear_selection();
+        let group_count = plan.files.len();
+        for (idx, stat) in self.stats.iter().enumerate() {
+            if let Some(keys) = plan.files.get(&stat.group_id) {
+                self.selected_groups[idx] = true;
+                self.selected_files.insert(stat.group_id.clone(), keys.clone());
             }
         }
-        self.apply_filter();
-    }
+        self.invalidate_view();
 
-    pub fn apply_filter(&mut self) {
-        let now = chrono::Utc::now().timestamp();
+        let message = t!(
+            "log.budget_selected",
+            format_bytes(plan.usage),
+            format_bytes(budget),
+            plan.evicted,
+            group_count,
+            format_bytes(plan.freed)
+        );
+        self.add_log(LogLevel::Info, &message);
+        if plan.remaining() > budget {
+            let message = t!("log.budget_unreachable", format_bytes(plan.remaining()));
+            self.add_log(LogLevel::Warning, &message);
+        }
+    }
 
-        self.filtered_stats = self.stats
-            .iter()
-            .enumerate()
-            .filter(|(_, stat)| {
-                if self.filter.hide_empty && stat.exist_count == 0 {
-                    return false;
-                }
+    pub fn start_budget_input(&mut self) {
+        self.budget_input = Some(String::new());
+        self.budget_input_error = None;
+    }
 
-                if stat.total_size < self.filter.min_size {
-                    return false;
-                }
+    pub fn cancel_budget_input(&mut self) {
+        self.budget_input = None;
+        self.budget_input_error = None;
+    }
 
-                if stat.file_count < self.filter.min_file_count {
-                    return false;
-                }
+    pub fn budget_input_push(&mut self, c: char) {
+        if let Some(ref mut input) = self.budget_input {
+            input.push(c);
+            self.budget_input_error = None;
+        }
+    }
 
-                match self.filter.activity {
-                    ActivityFilter::All => {}
-                    ActivityFilter::Active(days) => {
-                        let cutoff = now - (days * 86400);
-                        let latest_time = stat.files.iter()
-                            .map(|f| f.msg_time)
-                            .max()
-                            .unwrap_or(0);
-
-                        if latest_time < cutoff {
-                            return false;
-                        }
-                    }
-                    ActivityFilter::Inactive(days) => {
-                        let cutoff = now - (days * 86400);
-                        let latest_time = stat.files.iter()
-                            .map(|f| f.msg_time)
-                            .max()
-                            .unwrap_or(0);
-
-                        if latest_time >= cutoff {
-                            return false;
-                        }
-                    }
-                }
+    pub fn budget_input_pop(&mut self) {
+        if let Some(ref mut input) = self.budget_input {
+            input.pop();
+            self.budget_input_error = None;
+        }
+    }
 
-                true
-            })
-            .map(|(idx, _)| idx)
-            .collect();
+    pub fn submit_budget_input(&mut self) {
+        let Some(input) = self.budget_input.clone() else {
+            return;
+        };
 
-        if self.selected_index >= self.filtered_stats.len() {
-            self.selected_index = self.filtered_stats.len().saturating_sub(1);
+        match parse_size(&input) {
+            Some(budget) => {
+                self.cancel_budget_input();
+                self.select_for_budget(budget);
+            }
+            None => self.budget_input_error = Some(t!("ui.invalid_size", input.trim())),
         }
     }
 
-    pub fn add_log(&mut self, level: LogLevel, message: &str) {
-        let level_str = match level {
-            LogLevel::Info => "INFO",
-            LogLevel::Success => "OK",
-            LogLevel::Warning => "WARN",
-            LogLevel::Error => "ERR",
+    pub fn deselect_all(&mut self) {
+        self.selected_groups.fill(false);
+        self.selected_files.clear();
+        self.invalidate_view();
+        self.add_log(LogLevel::Info, t!("log.deselected_all"));
+    }
+
+    /// 操作完成后清空群组和文件选择
+    pub fn clear_selection(&mut self) {
+        self.selected_groups = vec![false; self.stats.len()];
+        self.selected_files.clear();
+        self.invalidate_view();
+    }
+
+    pub fn open_file_view(&mut self) {
+        let Some(&stat_idx) = self.filtered_stats.get(self.selected_index) else {
+            return;
         };
-        let _ = self.logger.log(level_str, message);
+
+        self.file_view = Some(FileView {
+            group_id: self.stats[stat_idx].group_id.clone(),
+            order: Vec::new(),
+            selected_index: 0,
+            sort_by: FileSortBy::Time,
+            descending: false,
+        });
+        self.refresh_file_view();
     }
 
-    pub fn start_operation(&mut self, total: usize) {
-        self.progress = OperationProgress {
-            total,
-            current: 0,
-            current_file: String::new(),
-            is_running: true,
+    pub fn close_file_view(&mut self) {
+        self.file_view = None;
+    }
+
+    /// 文件列表对应的群组下标
+    pub fn file_view_stat_index(&self) -> Option<usize> {
+        let view = self.file_view.as_ref()?;
+        self.stats.iter().position(|s| s.group_id == view.group_id)
+    }
+
+    /// 重新计算文件列表的排序（群组统计更新或排序方式变化后调用）
+    pub fn refresh_file_view(&mut self) {
+        let Some(stat_idx) = self.file_view_stat_index() else {
+            self.file_view = None;
+            return;
+        };
+
+        let files = &self.stats[stat_idx].files;
+        let Some(view) = self.file_view.as_mut() else {
+            return;
         };
+
+        let mut order: Vec<usize> = (0..files.len()).collect();
+        match view.sort_by {
+            FileSortBy::Time => order.sort_by_key(|&i| files[i].msg_time),
+            FileSortBy::Size => order.sort_by_key(|&i| files[i].actual_size.unwrap_or(0)),
+            FileSortBy::Name => order.sort_by(|&a, &b| files[a].file_name.cmp(&files[b].file_name)),
+            FileSortBy::MediaType => {
+                order.sort_by_key(|&i| (files[i].media_type().description(), files[i].msg_time))
+            }
+        }
+        if view.descending {
+            order.reverse();
+        }
+
+        view.order = order;
+        if view.selected_index >= view.order.len() {
+            view.selected_index = view.order.len().saturating_sub(1);
+        }
     }
 
-    pub fn update_progress(&mut self, current: usize, file: &str) {
-        self.progress.current = current;
-        self.progress.current_file = file.to_string();
+    pub fn file_view_next(&mut self) {
+        if let Some(view) = self.file_view.as_mut() {
+            if !view.order.is_empty() {
+                view.selected_index = (view.selected_index + 1) % view.order.len();
+            }
+        }
     }
 
-    pub fn finish_operation(&mut self) {
-        self.progress.is_running = false;
+    pub fn file_view_prev(&mut self) {
+        if let Some(view) = self.file_view.as_mut() {
+            if !view.order.is_empty() {
+                view.selected_index = if view.selected_index == 0 {
+                    view.order.len() - 1
+                } else {
+                    view.selected_index - 1
+                };
+            }
+        }
     }
 
-    pub fn toggle_help(&mut self) {
-        self.show_help = !self.show_help;
+    pub fn cycle_file_sort(&mut self) {
+        if let Some(view) = self.file_view.as_mut() {
+            view.sort_by = view.sort_by.next();
+        }
+        self.refresh_file_view();
     }
 
-    pub fn show_confirm(&mut self, action: ConfirmAction) {
-        self.confirm_action = Some(action);
-        self.show_confirm_dialog = true;
-        if action == ConfirmAction::Migrate {
-            self.temp_migrate_keep_original = true;
+    pub fn toggle_file_sort_direction(&mut self) {
+        if let Some(view) = self.file_view.as_mut() {
+            view.descending = !view.descending;
         }
+        self.refresh_file_view();
     }
 
-    pub fn hide_confirm(&mut self) {
-        self.show_confirm_dialog = false;
-        self.confirm_action = None;
+    /// 文件列表中当前高亮的文件
+    pub fn current_file(&self) -> Option<&FileInfo> {
+        let view = self.file_view.as_ref()?;
+        let stat_idx = self.file_view_stat_index()?;
+        let file_idx = *view.order.get(view.selected_index)?;
+        self.stats[stat_idx].files.get(file_idx)
     }
 
-    pub fn toggle_confirm_migrate_option(&mut self) {
-        self.temp_migrate_keep_original = !self.temp_migrate_keep_original;
+    pub fn toggle_preview(&mut self) {
+        self.preview_enabled = !self.preview_enabled;
     }
 
-    pub fn get_migrate_keep_original(&self) -> bool {
-        self.temp_migrate_keep_original
+    pub fn toggle_details(&mut self) {
+        self.details_enabled = !self.details_enabled;
     }
 
-    pub fn selected_count(&self) -> usize {
-        self.selected_groups.iter().filter(|&&x| x).count()
+    /// 群组列表中当前高亮的群
+    pub fn current_group(&self) -> Option<&GroupStats> {
+        let stat_idx = *self.filtered_stats.get(self.selected_index)?;
+        self.stats.get(stat_idx)
     }
 
-    pub fn selected_total_size(&self) -> u64 {
-        self.selected_groups
-            .iter()
-            .enumerate()
-            .filter_map(|(idx, &selected)| {
-                if selected {
-                    self.stats.get(idx).map(|s| s.total_size)
-                } else {
-                    None
-                }
-            })
-            .sum()
+    /// 需要预览的图片；对话框打开时不显示，避免图片覆盖在对话框上
+    pub fn preview_target(&self) -> Option<PathBuf> {
+        if !self.preview_enabled
+            || self.show_help
+            || self.show_confirm_dialog
+            || self.show_filter_dialog
+            || !matches!(self.current_tab, AppTab::Clean | AppTab::Migrate)
+        {
+            return None;
+        }
+
+        preview::preview_path(&self.qq_data_dir, self.current_file()?)
     }
 
-    pub fn selected_deletable_size(&self) -> u64 {
+    pub fn is_file_selected(&self, group_id: &str, file: &FileInfo) -> bool {
+        self.selected_files
+            .get(group_id)
+            .map(|keys| keys.contains(&file.key()))
+            .unwrap_or(false)
+    }
+
+    pub fn toggle_selected_file(&mut self) {
+        let Some(stat_idx) = self.file_view_stat_index() else {
+            return;
+        };
+        let Some(key) = self.current_file().map(|f| f.key()) else {
+            return;
+        };
+
+        let group_id = self.stats[stat_idx].group_id.clone();
+        let keys = self.selected_files.entry(group_id).or_default();
+        if !keys.remove(&key) {
+            keys.insert(key);
+        }
+        self.sync_group_selection(stat_idx);
+    }
+
+    /// 选择文件列表中当前条件范围内的所有文件
+    pub fn select_all_files(&mut self) {
+        let Some(stat_idx) = self.file_view_stat_index() else {
+            return;
+        };
+
+        let stat = &self.stats[stat_idx];
+        let filter = self.rule_filter_for(stat);
+        let keys: HashSet<String> = stat
+            .files
+            .iter()
+            .filter(|f| f.actual_size.is_some() && filter.matches(f))
+            .map(|f| f.key())
+            .collect();
+
+        self.selected_files.insert(stat.group_id.clone(), keys);
+        self.sync_group_selection(stat_idx);
+    }
+
+    pub fn clear_selected_files(&mut self) {
+        let Some(stat_idx) = self.file_view_stat_index() else {
+            return;
+        };
+
+        self.selected_files.remove(&self.stats[stat_idx].group_id);
+        self.sync_group_selection(stat_idx);
+    }
+
+    /// 群组是否被选中由其文件选择决定：有文件被选中即选中，全部取消则取消
+    fn sync_group_selection(&mut self, stat_idx: usize) {
+        let group_id = &self.stats[stat_idx].group_id;
+        let has_files = self
+            .selected_files
+            .get(group_id)
+            .map(|keys| !keys.is_empty())
+            .unwrap_or(false);
+
+        if !has_files {
+            self.selected_files.remove(group_id);
+        }
+        if let Some(selected) = self.selected_groups.get_mut(stat_idx) {
+            *selected = has_files;
+        }
+        self.invalidate_view();
+    }
+
+    pub fn cycle_sort(&mut self) {
+        self.sort_by = self.sort_by.next();
+        self.sort_descending = self.sort_by.default_descending();
+        self.apply_sort();
+        self.log_sort();
+    }
+
+    pub fn toggle_sort_direction(&mut self) {
+        self.sort_descending = !self.sort_descending;
+        self.apply_sort();
+        self.log_sort();
+    }
+
+    fn log_sort(&mut self) {
+        let message = t!("log.sort_by", self.sort_by.description(), self.sort_arrow());
+        self.add_log(LogLevel::Info, &message);
+    }
+
+    pub fn sort_arrow(&self) -> &'static str {
+        if self.sort_descending { "↓" } else { "↑" }
+    }
+
+    /// 按当前排序重排 stats，选择状态和光标所在的群跟随移动
+    pub fn apply_sort(&mut self) {
+        // 清理等操作更新群组数据后都会重新排序，按大小排序前先丢弃旧的统计
+        self.invalidate_view();
+        let current = self
+            .filtered_stats
+            .get(self.selected_index)
+            .map(|&idx| self.stats[idx].group_id.clone());
+
+        let stats = &self.stats;
+        let mut order: Vec<usize> = (0..stats.len()).collect();
+        match self.sort_by {
+            SortBy::Size => order.sort_by_cached_key(|&i| self.group_size_in_range(&stats[i])),
+            // 使用存在的文件数量排序，而非总文件数
+            SortBy::FileCount => order.sort_by_key(|&i| stats[i].exist_count),
+            SortBy::Name => order.sort_by(|&a, &b| stats[a].group_name.cmp(&stats[b].group_name)),
+            SortBy::LastActivity => order.sort_by_cached_key(|&i| stats[i].last_activity()),
+            SortBy::MissingCount => order.sort_by_key(|&i| stats[i].missing_count),
+            // 缺少群资料的群视为最小
+            SortBy::MemberCount => {
+                order.sort_by_key(|&i| stats[i].info.as_ref().map(|info| info.member_count))
+            }
+            SortBy::CreateTime => {
+                order.sort_by_key(|&i| stats[i].info.as_ref().map(|info| info.create_time))
+            }
+        }
+        if self.sort_descending {
+            order.reverse();
+        }
+
+        let mut stats: Vec<Option<GroupStats>> = std::mem::take(&mut self.stats).into_iter().map(Some).collect();
+        let selected = std::mem::take(&mut self.selected_groups);
+        self.stats = order.iter().filter_map(|&i| stats[i].take()).collect();
+        self.selected_groups = order.iter().map(|&i| selected.get(i).copied().unwrap_or(false)).collect();
+
+        self.apply_filter();
+
+        if let Some(group_id) = current {
+            if let Some(pos) = self
+                .filtered_stats
+                .iter()
+                .position(|&idx| self.stats[idx].group_id == group_id)
+            {
+                self.selected_index = pos;
+            }
+        }
+    }
+
+    pub fn apply_filter(&mut self) {
+        let now = chrono::Utc::now().timestamp();
+
+        let mut matched: Vec<(usize, i64)> = self.stats
+            .iter()
+            .enumerate()
+            .filter(|(_, stat)| self.filter.matches(stat, now))
+            .filter_map(|(idx, stat)| self.search_score(stat).map(|score| (idx, score)))
+            .collect();
+
+        if !self.search_query.is_empty() {
+            // 搜索时按匹配程度排序，同分保持原有排序
+            matched.sort_by_key(|&(_, score)| std::cmp::Reverse(score));
+        }
+        self.filtered_stats = matched.into_iter().map(|(idx, _)| idx).collect();
+
+        if self.selected_index >= self.filtered_stats.len() {
+            self.selected_index = self.filtered_stats.len().saturating_sub(1);
+        }
+
+        if self.file_view.is_some() {
+            self.refresh_file_view();
+        }
+    }
+
+    /// 群名、群备注、群号中最好的匹配得分，不匹配时返回 None
+    pub fn search_score(&self, stat: &GroupStats) -> Option<i64> {
+        if self.search_query.is_empty() {
+            return Some(0);
+        }
+
+        [
+            Some(stat.group_name.as_str()),
+            stat.group_remark(),
+            Some(stat.group_id.as_str()),
+        ]
+        .into_iter()
+        .flatten()
+        .filter_map(|text| fuzzy_match(&self.search_query, text))
+        .map(|m| m.score)
+        .max()
+    }
+
+    pub fn start_search(&mut self) {
+        self.search_active = true;
+    }
+
+    pub fn finish_search(&mut self) {
+        self.search_active = false;
+    }
+
+    pub fn clear_search(&mut self) {
+        self.search_active = false;
+        self.search_query.clear();
+        self.apply_filter();
+    }
+
+    pub fn search_push(&mut self, c: char) {
+        self.search_query.push(c);
+        self.selected_index = 0;
+        self.apply_filter();
+    }
+
+    pub fn search_pop(&mut self) {
+        self.search_query.pop();
+        self.apply_filter();
+    }
+
+    pub fn set_time_range(&mut self, range: TimeRange) {
+        self.time_range = range;
+        if self.sort_by == SortBy::Size {
+            self.apply_sort();
+        }
+        let message = t!("log.time_range", self.time_range.description());
+        self.add_log(LogLevel::Info, &message);
+    }
+
+    pub fn cycle_original_filter(&mut self) {
+        self.original_filter = self.original_filter.next();
+        if self.sort_by == SortBy::Size {
+            self.apply_sort();
+        }
+        let message = t!("log.original_filter", self.original_filter.description());
+        self.add_log(LogLevel::Info, &message);
+    }
+
+    pub fn cycle_group_policy(&mut self) {
+        self.group_policy = self.group_policy.next_preset();
+        if self.sort_by == SortBy::Size {
+            self.apply_sort();
+        }
+        let message = t!("log.group_policy", self.group_policy.description());
+        self.add_log(LogLevel::Info, &message);
+    }
+
+    pub fn cycle_clean_scope(&mut self) {
+        self.clean_scope = match self.clean_scope {
+            CleanScope::Both => CleanScope::OriOnly,
+            CleanScope::OriOnly => CleanScope::ThumbOnly,
+            CleanScope::ThumbOnly => CleanScope::Both,
+        };
+        let message = t!("log.clean_scope", self.clean_scope.description());
+        self.add_log(LogLevel::Info, &message);
+    }
+
+    pub fn start_time_input(&mut self) {
+        self.time_input = Some(String::new());
+        self.time_input_error = None;
+    }
+
+    pub fn cancel_time_input(&mut self) {
+        self.time_input = None;
+        self.time_input_error = None;
+    }
+
+    pub fn time_input_push(&mut self, c: char) {
+        if let Some(ref mut input) = self.time_input {
+            input.push(c);
+            self.time_input_error = None;
+        }
+    }
+
+    pub fn time_input_pop(&mut self) {
+        if let Some(ref mut input) = self.time_input {
+            input.pop();
+            self.time_input_error = None;
+        }
+    }
+
+    /// 解析输入的时间范围，失败时保留输入并显示原因
+    pub fn submit_time_input(&mut self) {
+        let Some(input) = self.time_input.clone() else {
+            return;
+        };
+
+        match TimeRange::parse(&input) {
+            Ok(range) => {
+                self.cancel_time_input();
+                self.set_time_range(range);
+            }
+            Err(e) => self.time_input_error = Some(e.to_string()),
+        }
+    }
+
+    pub fn add_log(&mut self, level: LogLevel, message: &str) {
+        let _ = self.logger.log(level.label(), message);
+
+        if self.logs.len() == LOG_BUFFER_SIZE {
+            self.logs.pop_front();
+        }
+        self.logs.push_back(LogEntry {
+            time: chrono::Local::now().format("%H:%M:%S").to_string(),
+            level,
+            message: message.to_string(),
+        });
+
+        // 正在查看历史日志时保持位置不动
+        if self.log_scroll > 0 && self.log_filter.matches(level) {
+            self.log_scroll += 1;
+        }
+    }
+
+    /// 当前级别过滤下可见的日志
+    pub fn visible_logs(&self) -> Vec<&LogEntry> {
+        self.logs
+            .iter()
+            .filter(|entry| self.log_filter.matches(entry.level))
+            .collect()
+    }
+
+    pub fn cycle_log_filter(&mut self) {
+        self.log_filter = self.log_filter.next();
+        self.log_scroll = 0;
+    }
+
+    /// 向更早的日志滚动
+    pub fn scroll_logs_up(&mut self, lines: usize) {
+        let max = self.visible_logs().len().saturating_sub(1);
+        self.log_scroll = (self.log_scroll + lines).min(max);
+    }
+
+    pub fn scroll_logs_down(&mut self, lines: usize) {
+        self.log_scroll = self.log_scroll.saturating_sub(lines);
+    }
+
+    /// 时间分布图统计的群：开启"只看已选"且有选择时为已选择的群，否则为全部
+    fn chart_groups(&self) -> Vec<&GroupStats> {
+        let selected_only = self.chart_selected_only && self.selected_count() > 0;
+        self.stats
+            .iter()
+            .enumerate()
+            .filter(|&(idx, _)| !selected_only || self.selected_groups.get(idx).copied().unwrap_or(false))
+            .map(|(_, stat)| stat)
+            .collect()
+    }
+
+    /// 按天（UTC）统计时间范围内现存文件的大小和数量
+    pub fn daily_activity(&self) -> BTreeMap<NaiveDate, Activity> {
+        let mut days: BTreeMap<NaiveDate, Activity> = BTreeMap::new();
+
+        for stat in self.chart_groups() {
+            let filter = self.view_filter_for(stat);
+            for file in stat.files.iter().filter(|file| filter.matches(file)) {
+                let (Some(size), Some(datetime)) =
+                    (file.actual_size, DateTime::<Utc>::from_timestamp(file.msg_time, 0))
+                else {
+                    continue;
+                };
+                let day = days.entry(datetime.date_naive()).or_default();
+                day.bytes += size;
+                day.count += 1;
+            }
+        }
+
+        days
+    }
+
+    /// 按月汇总，从最早到最晚的月份连续排列，没有文件的月份为空
+    pub fn monthly_activity(&self) -> Vec<(String, Activity)> {
+        let days = self.daily_activity();
+        let month_index = |date: &NaiveDate| date.year() * 12 + date.month0() as i32;
+
+        let (Some(first), Some(last)) = (days.keys().next(), days.keys().next_back()) else {
+            return Vec::new();
+        };
+        let first = month_index(first);
+
+        let mut months: Vec<(String, Activity)> = (first..=month_index(last))
+            .map(|m| (format!("{}-{:02}", m.div_euclid(12), m.rem_euclid(12) + 1), Activity::default()))
+            .collect();
+        for (date, activity) in &days {
+            let month = &mut months[(month_index(date) - first) as usize].1;
+            month.bytes += activity.bytes;
+            month.count += activity.count;
+        }
+
+        months
+    }
+
+    /// 热力图的周数（从最早一天所在的周到最晚一天所在的周）
+    pub fn activity_weeks(&self) -> usize {
+        let days = self.daily_activity();
+        match (days.keys().next(), days.keys().next_back()) {
+            (Some(first), Some(last)) => {
+                let monday = |date: &NaiveDate| *date - chrono::Days::new(date.weekday().num_days_from_monday() as u64);
+                ((monday(last) - monday(first)).num_days() / 7 + 1) as usize
+            }
+            _ => 0,
+        }
+    }
+
+    pub fn cycle_chart_metric(&mut self) {
+        self.chart_metric = self.chart_metric.next();
+    }
+
+    pub fn toggle_chart_selected(&mut self) {
+        self.chart_selected_only = !self.chart_selected_only;
+        self.chart_scroll = 0;
+        if self.chart_selected_only && self.selected_count() == 0 {
+            self.add_log(LogLevel::Warning, t!("log.chart_no_selection"));
+        }
+    }
+
+    pub fn toggle_chart_view(&mut self) {
+        self.chart_view = match self.chart_view {
+            ChartView::Monthly => ChartView::Heatmap,
+            ChartView::Heatmap => ChartView::Monthly,
+        };
+        self.chart_scroll = 0;
+    }
+
+    /// 向更早的月份（热力图为周）滚动
+    pub fn scroll_chart_older(&mut self, columns: usize) {
+        let total = match self.chart_view {
+            ChartView::Monthly => self.monthly_activity().len(),
+            ChartView::Heatmap => self.activity_weeks(),
+        };
+        self.chart_scroll = (self.chart_scroll + columns).min(total.saturating_sub(1));
+    }
+
+    pub fn scroll_chart_newer(&mut self, columns: usize) {
+        self.chart_scroll = self.chart_scroll.saturating_sub(columns);
+    }
+
+    /// 树图当前层的各块，从大到小排列
+    ///
+    /// 顶层为各群（按 total_size），进入群后按年，再进入某年后按月。
+    pub fn treemap_tiles(&self) -> Vec<Tile> {
+        const MAX_GROUP_TILES: usize = 40;
+
+        let Some(ref group_id) = self.treemap_group else {
+            let mut groups: Vec<&GroupStats> = self.stats.iter().filter(|s| s.total_size > 0).collect();
+            groups.sort_by_key(|stat| std::cmp::Reverse(stat.total_size));
+
+            let mut tiles: Vec<Tile> = groups
+                .iter()
+                .take(MAX_GROUP_TILES)
+                .map(|stat| Tile {
+                    label: stat.group_name.clone(),
+                    size: stat.total_size,
+                    target: TileTarget::Group(stat.group_id.clone()),
+                })
+                .collect();
+
+            let rest = &groups[groups.len().min(MAX_GROUP_TILES)..];
+            if !rest.is_empty() {
+                tiles.push(Tile {
+                    label: t!("ui.treemap_others", rest.len()),
+                    size: rest.iter().map(|s| s.total_size).sum(),
+                    target: TileTarget::Others,
+                });
+            }
+            return tiles;
+        };
+
+        let Some(stat) = self.stats.iter().find(|s| &s.group_id == group_id) else {
+            return Vec::new();
+        };
+
+        // 按 UTC 年月划分，与时间分布图一致
+        let mut periods: BTreeMap<(i32, u32), u64> = BTreeMap::new();
+        for file in &stat.files {
+            let (Some(size), Some(datetime)) =
+                (file.actual_size, DateTime::<Utc>::from_timestamp(file.msg_time, 0))
+            else {
+                continue;
+            };
+            let key = match self.treemap_year {
+                Some(year) if datetime.year() != year => continue,
+                Some(year) => (year, datetime.month()),
+                None => (datetime.year(), 0),
+            };
+            *periods.entry(key).or_default() += size;
+        }
+
+        let mut tiles: Vec<Tile> = periods
+            .into_iter()
+            .map(|((year, month), size)| match self.treemap_year {
+                Some(_) => Tile {
+                    label: format!("{}-{:02}", year, month),
+                    size,
+                    target: TileTarget::Month(year, month),
+                },
+                None => Tile {
+                    label: year.to_string(),
+                    size,
+                    target: TileTarget::Year(year),
+                },
+            })
+            .collect();
+        tiles.sort_by_key(|tile| std::cmp::Reverse(tile.size));
+        tiles
+    }
+
+    pub fn treemap_next(&mut self) {
+        let count = self.treemap_tiles().len();
+        if count > 0 {
+            self.treemap_cursor = (self.treemap_cursor + 1) % count;
+        }
+    }
+
+    pub fn treemap_prev(&mut self) {
+        let count = self.treemap_tiles().len();
+        if count > 0 {
+            self.treemap_cursor = (self.treemap_cursor + count - 1) % count;
+        }
+    }
+
+    /// 进入当前块：群进入按年划分，年份进入按月划分
+    pub fn treemap_open(&mut self) {
+        let Some(tile) = self.treemap_tiles().into_iter().nth(self.treemap_cursor) else {
+            return;
+        };
+
+        match tile.target {
+            TileTarget::Group(group_id) => self.treemap_group = Some(group_id),
+            TileTarget::Year(year) => self.treemap_year = Some(year),
+            TileTarget::Month(..) | TileTarget::Others => return,
+        }
+        self.treemap_cursor = 0;
+    }
+
+    /// 返回上一层，光标停在刚才进入的块上
+    pub fn treemap_back(&mut self) {
+        let parent = if let Some(year) = self.treemap_year.take() {
+            TileTarget::Year(year)
+        } else if let Some(group_id) = self.treemap_group.take() {
+            TileTarget::Group(group_id)
+        } else {
+            return;
+        };
+
+        self.treemap_cursor = self
+            .treemap_tiles()
+            .iter()
+            .position(|tile| tile.target == parent)
+            .unwrap_or(0);
+    }
+
+    /// 在清理页的群组列表中定位树图当前所在（或光标所在）的群
+    pub fn jump_to_treemap_group(&mut self) {
+        let group_id = match self.treemap_group.clone() {
+            Some(group_id) => group_id,
+            None => match self.treemap_tiles().into_iter().nth(self.treemap_cursor) {
+                Some(Tile { target: TileTarget::Group(group_id), .. }) => group_id,
+                _ => return,
+            },
+        };
+
+        let position = |app: &Self| {
+            app.filtered_stats
+                .iter()
+                .position(|&idx| app.stats[idx].group_id == group_id)
+        };
+
+        // 搜索或过滤器把它隐藏时先清除搜索，仍然找不到再提示
+        if position(self).is_none() && !self.search_query.is_empty() {
+            self.clear_search();
+        }
+        let Some(pos) = position(self) else {
+            self.add_log(LogLevel::Warning, t!("log.jump_hidden_by_filter"));
+            return;
+        };
+
+        self.file_view = None;
+        self.selected_index = pos;
+        self.set_tab(AppTab::Clean);
+    }
+
+    pub fn start_operation(&mut self, total: usize) {
+        self.progress = OperationProgress {
+            total,
+            current: 0,
+            current_file: String::new(),
+            is_running: true,
+        };
+    }
+
+    pub fn update_progress(&mut self, current: usize, file: &str) {
+        self.progress.current = current;
+        self.progress.current_file = file.to_string();
+    }
+
+    pub fn finish_operation(&mut self) {
+        self.progress.is_running = false;
+    }
+
+    pub fn toggle_help(&mut self) {
+        self.show_help = !self.show_help;
+    }
+
+    pub fn show_confirm(&mut self, action: ConfirmAction) {
+        self.confirm_action = Some(action);
+        self.show_confirm_dialog = true;
+        if action == ConfirmAction::Migrate {
+            self.temp_migrate_mode = MigrateMode::Copy;
+        }
+        self.refresh_preflight();
+    }
+
+    pub fn hide_confirm(&mut self) {
+        self.show_confirm_dialog = false;
+        self.confirm_action = None;
+        self.preflight_issues.clear();
+    }
+
+    pub fn toggle_confirm_migrate_option(&mut self) {
+        self.temp_migrate_mode = self.temp_migrate_mode.next();
+        self.refresh_preflight();
+    }
+
+    /// 根据当前确认的操作重新运行预检查，结果显示在确认对话框中
+    pub fn refresh_preflight(&mut self) {
+        let Some(action) = self.confirm_action else {
+            self.preflight_issues.clear();
+            return;
+        };
+
+        let request = match action {
+            ConfirmAction::Clean => PreflightRequest {
+                qq_data_dir: &self.qq_data_dir,
+                writes_source: true,
+                target_dir: None,
+                required_bytes: 0,
+                writes_back: false,
+            },
+            ConfirmAction::Migrate => PreflightRequest {
+                qq_data_dir: &self.qq_data_dir,
+                writes_source: self.temp_migrate_mode.modifies_source(),
+                target_dir: Some(&self.migrate_target_path),
+                // 只有时间范围内的文件会被复制到目标路径
+                required_bytes: self.selected_deletable_size(),
+                writes_back: false,
+            },
+            ConfirmAction::Restore => PreflightRequest {
+                qq_data_dir: &self.qq_data_dir,
+                writes_source: true,
+                target_dir: Some(&self.migrate_target_path),
+                required_bytes: self.selected_restore_size(),
+                writes_back: true,
+            },
+        };
+
+        self.preflight_issues = preflight::run(&request);
+    }
+
+    pub fn get_migrate_mode(&self) -> MigrateMode {
+        self.temp_migrate_mode
+    }
+
+    pub fn request_doctor(&mut self) {
+        self.doctor_requested = true;
+    }
+
+    pub fn selected_count(&self) -> usize {
+        self.selected_groups.iter().filter(|&&x| x).count()
+    }
+
+    pub fn selected_total_size(&self) -> u64 {
+        self.selected_groups
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, &selected)| {
+                if selected {
+                    self.stats.get(idx).map(|s| s.total_size)
+                } else {
+                    None
+                }
+            })
+            .sum()
+    }
+
+    pub fn selected_linked_size(&self) -> u64 {
+        self.selected_groups
+            .iter()
+            .enumerate()
+            .filter_map(|(idx, &selected)| {
+                if selected {
+                    self.stats.get(idx).map(|s| s.linked_size)
+                } else {
+                    None
+                }
+            })
+            .sum()
+    }
+
+    /// 当前时间范围等条件下参与清理/迁移的文件筛选器
+    pub fn file_filter(&self) -> FileFilter {
+        FileFilter::new(self.time_range)
+            .with_original(self.original_filter)
+            .with_rules(self.filter.files.clone())
+    }
+
+    /// 某个群组按条件得到的筛选器，不考虑手动选择的文件；在清理页面时同时应用清理策略
+    pub fn rule_filter_for(&self, stat: &GroupStats) -> FileFilter {
+        if matches!(self.current_tab, AppTab::Clean) {
+            self.policy_filter_for(stat)
+        } else {
+            self.file_filter()
+        }
+    }
+
+    /// 按条件和清理策略得到的筛选器
+    fn policy_filter_for(&self, stat: &GroupStats) -> FileFilter {
+        let filter = self.file_filter();
+        match self.group_policy.select(stat, &filter) {
+            Some(keys) => filter.with_files(keys),
+            None => filter,
+        }
+    }
+
+    /// 迁移和恢复使用的筛选器：在文件列表中手动选择过文件时只包含这些文件，清理策略不参与
+    pub fn file_filter_for(&self, stat: &GroupStats) -> FileFilter {
+        match self.selected_files.get(&stat.group_id) {
+            Some(keys) => self.file_filter().with_files(keys.clone()),
+            None => self.file_filter(),
+        }
+    }
+
+    /// 清理使用的筛选器：没有手动选择文件时按清理策略挑选
+    pub fn clean_filter_for(&self, stat: &GroupStats) -> FileFilter {
+        match self.selected_files.get(&stat.group_id) {
+            Some(keys) => self.file_filter().with_files(keys.clone()),
+            None => self.policy_filter_for(stat),
+        }
+    }
+
+    /// 统计和图表使用的筛选器，与当前页面的操作一致
+    fn view_filter_for(&self, stat: &GroupStats) -> FileFilter {
+        if matches!(self.current_tab, AppTab::Clean) {
+            self.clean_filter_for(stat)
+        } else {
+            self.file_filter_for(stat)
+        }
+    }
+
+    pub fn selected_deletable_size(&self) -> u64 {
         self.selected_groups
             .iter()
             .enumerate()
@@ -381,38 +1693,126 @@ impl App {
                     None
                 }
             })
-            .flat_map(|stat| &stat.files)
-            .filter(|file| self.time_range.should_delete(file.msg_time))
-            .filter_map(|file| file.actual_size)
+            .map(|stat| self.group_size_in_range(stat))
             .sum()
     }
 
-    pub fn group_size_in_range(&self, stat: &GroupStats) -> u64 {
-        stat.files
+    /// 恢复已选群组时预计写回 QQ 目录的字节数
+    ///
+    /// 已清理的文件按数据库记录的大小估算；符号链接会被替换成真实文件，按链接部分的大小计算。
+    pub fn selected_restore_size(&self) -> u64 {
+        self.stats
             .iter()
-            .filter(|file| self.time_range.should_delete(file.msg_time))
-            .filter_map(|file| file.actual_size)
+            .zip(&self.selected_groups)
+            .filter(|(_, &selected)| selected)
+            .map(|(stat, _)| {
+                let filter = self.file_filter_for(stat);
+                stat.files
+                    .iter()
+                    .filter(|file| filter.matches(file))
+                    .map(|file| match file.actual_size {
+                        Some(_) => file.linked_size,
+                        None => file.file_size.max(0) as u64,
+                    })
+                    .sum::<u64>()
+            })
             .sum()
     }
 
-    pub fn group_exist_count_in_range(&self, stat: &GroupStats) -> usize {
-        stat.files
+    /// 清理已选群组后实际释放的空间预测（已去重，不含符号链接）
+    ///
+    /// `all_if_none` 为真且没有选择任何群时，按全部群组计算。
+    pub fn reclaim_projection(&self, all_if_none: bool) -> Projection {
+        let slot = all_if_none as usize;
+        if let Some(projection) = self.with_view_cache(|cache| cache.projections[slot]) {
+            return projection;
+        }
+
+        let everything = all_if_none && self.selected_count() == 0;
+        let selected: HashSet<&str> = self
+            .stats
             .iter()
-            .filter(|file| file.actual_size.is_some())
-            .filter(|file| self.time_range.should_delete(file.msg_time))
-            .count()
+            .zip(&self.selected_groups)
+            .filter(|(_, &selected)| selected || everything)
+            .map(|(stat, _)| stat.group_id.as_str())
+            .collect();
+
+        let projection = projection::project(&self.stats, |stat| {
+            selected
+                .contains(stat.group_id.as_str())
+                .then(|| self.clean_filter_for(stat))
+        });
+        self.with_view_cache(|cache| cache.projections[slot] = Some(projection));
+        projection
+    }
+
+    pub fn group_size_in_range(&self, stat: &GroupStats) -> u64 {
+        self.range_counts(stat).size
+    }
+
+    pub fn group_exist_count_in_range(&self, stat: &GroupStats) -> usize {
+        self.range_counts(stat).exist_count
     }
 
     pub fn group_file_count_in_range(&self, stat: &GroupStats) -> usize {
-        stat.files
-            .iter()
-            .filter(|file| self.time_range.should_delete(file.msg_time))
-            .count()
+        self.range_counts(stat).file_count
+    }
+
+    fn range_counts(&self, stat: &GroupStats) -> RangeCounts {
+        if let Some(counts) = self.with_view_cache(|cache| cache.counts.get(&stat.group_id).copied()) {
+            return counts;
+        }
+
+        let filter = self.view_filter_for(stat);
+        let mut counts = RangeCounts {
+            size: 0,
+            exist_count: 0,
+            file_count: 0,
+        };
+        for file in stat.files.iter().filter(|file| filter.matches(file)) {
+            counts.file_count += 1;
+            if let Some(size) = file.actual_size {
+                counts.size += size;
+                counts.exist_count += 1;
+            }
+        }
+
+        self.with_view_cache(|cache| cache.counts.insert(stat.group_id.clone(), counts));
+        counts
+    }
+
+    /// 群组数据或选择变化后调用，条件和策略的变化会自动检测
+    fn invalidate_view(&mut self) {
+        self.view_revision += 1;
+    }
+
+    /// 访问统计缓存，条件与缓存时不同则先清空
+    fn with_view_cache<R>(&self, f: impl FnOnce(&mut ViewCache) -> R) -> R {
+        let key = ViewKey {
+            time_range: self.time_range,
+            original_filter: self.original_filter,
+            rules: self.filter.files.clone(),
+            group_policy: self.group_policy,
+            clean_view: matches!(self.current_tab, AppTab::Clean),
+            revision: self.view_revision,
+        };
+
+        let mut cache = self.view_cache.borrow_mut();
+        if cache.key.as_ref() != Some(&key) {
+            *cache = ViewCache {
+                key: Some(key),
+                ..ViewCache::default()
+            };
+        }
+        f(&mut cache)
     }
 
     pub fn next_migrate_path(&mut self) {
         self.migrate_path_index = (self.migrate_path_index + 1) % self.migrate_presets.len();
         self.migrate_target_path = self.migrate_presets[self.migrate_path_index].clone();
+        if self.show_confirm_dialog {
+            self.refresh_preflight();
+        }
     }
 
     pub fn prev_migrate_path(&mut self) {
@@ -422,19 +1822,28 @@ impl App {
             self.migrate_path_index -= 1;
         }
         self.migrate_target_path = self.migrate_presets[self.migrate_path_index].clone();
+        if self.show_confirm_dialog {
+            self.refresh_preflight();
+        }
     }
 
     pub fn open_filter_dialog(&mut self) {
         self.temp_filter = self.filter.clone();
         self.filter_cursor = 0;
+        self.filter_input = None;
+        self.filter_input_error = None;
         self.show_filter_dialog = true;
     }
 
     pub fn apply_filter_dialog(&mut self) {
         self.filter = self.temp_filter.clone();
         self.apply_filter();
+        // 文件条件会改变范围内大小
+        if self.sort_by == SortBy::Size {
+            self.apply_sort();
+        }
         self.show_filter_dialog = false;
-        self.add_log(LogLevel::Info, "过滤器已应用");
+        self.add_log(LogLevel::Info, t!("log.filter_applied"));
     }
 
     pub fn cancel_filter_dialog(&mut self) {
@@ -442,23 +1851,24 @@ impl App {
     }
 
     pub fn filter_next_item(&mut self) {
-        self.filter_cursor = (self.filter_cursor + 1) % 4;
+        self.filter_cursor = (self.filter_cursor + 1) % FilterOption::ALL.len();
     }
 
     pub fn filter_prev_item(&mut self) {
         if self.filter_cursor == 0 {
-            self.filter_cursor = 3;
+            self.filter_cursor = FilterOption::ALL.len() - 1;
         } else {
             self.filter_cursor -= 1;
         }
     }
 
+    /// 切换当前选项；文字输入类选项进入编辑状态，以当前值作为初始内容
     pub fn toggle_filter_option(&mut self) {
-        match self.filter_cursor {
-            0 => {
+        match FilterOption::ALL[self.filter_cursor] {
+            FilterOption::HideEmpty => {
                 self.temp_filter.hide_empty = !self.temp_filter.hide_empty;
             }
-            1 => {
+            FilterOption::Activity => {
                 self.temp_filter.activity = match self.temp_filter.activity {
                     ActivityFilter::All => ActivityFilter::Active(7),
                     ActivityFilter::Active(7) => ActivityFilter::Active(30),
@@ -469,7 +1879,127 @@ impl App {
                     ActivityFilter::Inactive(_) => ActivityFilter::All,
                 };
             }
-            _ => {}
+            FilterOption::MinSize => {
+                let current = match self.temp_filter.min_size {
+                    0 => String::new(),
+                    size => format_size_exact(size),
+                };
+                self.filter_input = Some(current);
+            }
+            FilterOption::MinFileCount => {
+                let current = match self.temp_filter.min_file_count {
+                    0 => String::new(),
+                    count => count.to_string(),
+                };
+                self.filter_input = Some(current);
+            }
+            FilterOption::NamePattern => {
+                let current = self.temp_filter.name_pattern.as_ref()
+                    .map(|p| p.0.as_str().to_string())
+                    .unwrap_or_default();
+                self.filter_input = Some(current);
+            }
+            FilterOption::Quit => {
+                self.temp_filter.quit = self.temp_filter.quit.next();
+            }
+            FilterOption::MediaType => {
+                self.temp_filter.media_type = match self.temp_filter.media_type {
+                    None => Some(MediaType::Image),
+                    Some(MediaType::Image) => Some(MediaType::Gif),
+                    Some(MediaType::Gif) => Some(MediaType::Sticker),
+                    Some(MediaType::Sticker) => None,
+                };
+            }
+            FilterOption::FileMinSize => {
+                let current = match self.temp_filter.files.min_size {
+                    0 => String::new(),
+                    size => format_size_exact(size),
+                };
+                self.filter_input = Some(current);
+            }
+            FilterOption::FileMaxSize => {
+                let current = self.temp_filter.files.max_size
+                    .map(format_size_exact)
+                    .unwrap_or_default();
+                self.filter_input = Some(current);
+            }
+            FilterOption::Extensions => {
+                let current = self.temp_filter.files.extensions.as_ref()
+                    .map(|extensions| extensions.join(","))
+                    .unwrap_or_default();
+                self.filter_input = Some(current);
+            }
+            FilterOption::Animation => {
+                self.temp_filter.files.animation = self.temp_filter.files.animation.next();
+            }
+        }
+        self.filter_input_error = None;
+    }
+
+    pub fn filter_input_push(&mut self, c: char) {
+        if let Some(ref mut input) = self.filter_input {
+            input.push(c);
+            self.filter_input_error = None;
+        }
+    }
+
+    pub fn filter_input_pop(&mut self) {
+        if let Some(ref mut input) = self.filter_input {
+            input.pop();
+            self.filter_input_error = None;
+        }
+    }
+
+    pub fn cancel_filter_input(&mut self) {
+        self.filter_input = None;
+        self.filter_input_error = None;
+    }
+
+    /// 把输入写回当前选项，留空表示不限制；无法解析时保留输入并显示原因
+    pub fn submit_filter_input(&mut self) {
+        let Some(input) = self.filter_input.clone() else {
+            return;
+        };
+        let value = input.trim();
+
+        let result = match FilterOption::ALL[self.filter_cursor] {
+            FilterOption::MinSize => match value {
+                "" => Ok(0),
+                _ => parse_size(value).ok_or_else(|| t!("ui.invalid_size", value)),
+            }
+            .map(|size| self.temp_filter.min_size = size),
+            FilterOption::MinFileCount => match value {
+                "" => Ok(0),
+                _ => value.parse().map_err(|_| t!("ui.invalid_count", value)),
+            }
+            .map(|count| self.temp_filter.min_file_count = count),
+            FilterOption::NamePattern => match value {
+                "" => Ok(None),
+                _ => Regex::new(value)
+                    .map(|regex| Some(NamePattern(regex)))
+                    .map_err(|e| t!("ui.invalid_regex", e)),
+            }
+            .map(|pattern| self.temp_filter.name_pattern = pattern),
+            FilterOption::FileMinSize => match value {
+                "" => Ok(0),
+                _ => parse_size(value).ok_or_else(|| t!("ui.invalid_size", value)),
+            }
+            .map(|size| self.temp_filter.files.min_size = size),
+            FilterOption::FileMaxSize => match value {
+                "" => Ok(None),
+                _ => parse_size(value).map(Some).ok_or_else(|| t!("ui.invalid_size", value)),
+            }
+            .map(|size| self.temp_filter.files.max_size = size),
+            FilterOption::Extensions => {
+                self.temp_filter.files.extensions = file_filter::parse_extensions(value);
+                Ok(())
+            }
+            _ => Ok(()),
+        };
+
+        match result {
+            Ok(()) => self.cancel_filter_input(),
+            Err(e) => self.filter_input_error = Some(e),
         }
     }
 }
@@ -0,0 +1,458 @@
+use anyhow::{bail, Context, Result};
+use std::collections::{HashMap, HashSet};
+use std::io::{self, BufRead, IsTerminal, Write};
+
+use crate::app::LogLevel;
+use crate::file_checker::FileChecker;
+use crate::file_filter::{self, AnimationFilter, FileFilter, FileRules, OriginalFilter};
+use crate::logger::Logger;
+use crate::policy::{self, GroupPolicy};
+use crate::preflight::{self, PreflightRequest};
+use crate::models::{format_bytes, parse_size, CleanScope, GroupStats};
+use crate::projection;
+use crate::time_range::TimeRange;
+
+/// 命令行子命令，不带参数时启动 TUI
+pub enum Command {
+    Tui,
+    Help,
+    Clean(CleanArgs),
+}
+
+/// `clean` 子命令的参数
+pub struct CleanArgs {
+    pub scope: CleanScope,
+    pub range: TimeRange,
+    pub original: OriginalFilter,
+    pub rules: FileRules,
+    pub policy: GroupPolicy,
+    pub budget: Option<u64>,  // 全局存储预算，按最久未使用淘汰全部群中的文件
+    pub pinned: Vec<String>,  // 预算淘汰时跳过的群号，会合并配置文件中的 pinned_groups
+    pub groups: Vec<String>, // 要清理的群号，与 all 二选一
+    pub all: bool,
+    pub dry_run: bool,       // 只输出预测，不删除文件
+    pub yes: bool,           // 跳过删除前的确认
+}
+
+/// 解析程序名之后的命令行参数
+pub fn parse(args: impl IntoIterator<Item = String>) -> Result<Command> {
+    let mut args = args.into_iter();
+    match args.next().as_deref() {
+        None => Ok(Command::Tui),
+        Some("help" | "-h" | "--help") => Ok(Command::Help),
+        Some("clean") => parse_clean(args),
+        Some(other) => bail!(t!("cli.unknown_command", other)),
+    }
+}
+
+fn parse_clean(mut args: impl Iterator<Item = String>) -> Result<Command> {
+    let mut parsed = CleanArgs {
+        scope: CleanScope::Both,
+        range: TimeRange::All,
+        original: OriginalFilter::All,
+        rules: FileRules::default(),
+        policy: GroupPolicy::None,
+        budget: None,
+        pinned: Vec::new(),
+        groups: Vec::new(),
+        all: false,
+        dry_run: false,
+        yes: false,
+    };
+
+    while let Some(arg) = args.next() {
+        // 同时支持 `--scope ori` 和 `--scope=ori`
+        let (flag, inline) = match arg.split_once('=') {
+            Some((flag, value)) if flag.starts_with("--") => (flag.to_string(), Some(value.to_string())),
+            _ => (arg, None),
+        };
+
+        match flag.as_str() {
+            "-h" | "--help" => return Ok(Command::Help),
+            "--all" => parsed.all = true,
+            "-n" | "--dry-run" => parsed.dry_run = true,
+            "-y" | "--yes" => parsed.yes = true,
+            "--scope" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                parsed.scope = parse_scope(&value)?;
+            }
+            "--range" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                parsed.range = TimeRange::parse(&value)?;
+            }
+            "--original" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                parsed.original = parse_original(&value)?;
+            }
+            "--min-size" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                parsed.rules.min_size = parse_size_arg(&value)?;
+            }
+            "--max-size" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                parsed.rules.max_size = Some(parse_size_arg(&value)?);
+            }
+            "--ext" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                parsed.rules.extensions = file_filter::parse_extensions(&value);
+            }
+            "--keep-newest" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                let count = value
+                    .trim()
+                    .parse()
+                    .map_err(|_| anyhow::anyhow!(t!("ui.invalid_count", value)))?;
+                set_policy(&mut parsed.policy, GroupPolicy::KeepNewest(count))?;
+            }
+            "--quota" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                set_policy(&mut parsed.policy, GroupPolicy::Quota(parse_size_arg(&value)?))?;
+            }
+            "--budget" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                parsed.budget = Some(parse_size_arg(&value)?);
+            }
+            "--pin" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                parsed.pinned.extend(split_ids(&value));
+            }
+            "--gif" => parsed.rules.animation = AnimationFilter::Animated,
+            "--static" => parsed.rules.animation = AnimationFilter::Static,
+            "--group" => {
+                let value = take_value(&flag, inline, &mut args)?;
+                parsed.groups.extend(split_ids(&value));
+            }
+            _ => bail!(t!("cli.unknown_option", flag)),
+        }
+    }
+
+    if parsed.all && !parsed.groups.is_empty() {
+        bail!(t!("cli.clean_conflicting_targets"));
+    }
+    if parsed.budget.is_some() {
+        // 预算作用于全部群，只能用 --pin 排除
+        if !parsed.groups.is_empty() || parsed.policy != GroupPolicy::None {
+            bail!(t!("cli.budget_conflict"));
+        }
+    } else if !parsed.all && parsed.groups.is_empty() {
+        bail!(t!("cli.clean_no_target"));
+    }
+
+    Ok(Command::Clean(parsed))
+}
+
+/// "123,456" -> ["123", "456"]
+fn split_ids(value: &str) -> impl Iterator<Item = String> + '_ {
+    value
+        .split(',')
+        .map(str::trim)
+        .filter(|id| !id.is_empty())
+        .map(String::from)
+}
+
+fn take_value(flag: &str, inline: Option<String>, args: &mut impl Iterator<Item = String>) -> Result<String> {
+    inline
+        .or_else(|| args.next())
+        .with_context(|| t!("cli.missing_value", flag))
+}
+
+fn parse_scope(value: &str) -> Result<CleanScope> {
+    match value.to_ascii_lowercase().as_str() {
+        "both" | "all" => Ok(CleanScope::Both),
+        "ori" | "original" | "originals" => Ok(CleanScope::OriOnly),
+        "thumb" | "thumbs" | "thumbnail" | "thumbnails" => Ok(CleanScope::ThumbOnly),
+        _ => bail!(t!("cli.unknown_scope", value)),
+    }
+}
+
+fn set_policy(current: &mut GroupPolicy, policy: GroupPolicy) -> Result<()> {
+    if *current != GroupPolicy::None {
+        bail!(t!("cli.conflicting_policies"));
+    }
+    *current = policy;
+    Ok(())
+}
+
+fn parse_size_arg(value: &str) -> Result<u64> {
+    match parse_size(value) {
+        Some(size) => Ok(size),
+        None => bail!(t!("ui.invalid_size", value)),
+    }
+}
+
+fn parse_original(value: &str) -> Result<OriginalFilter> {
+    match value.to_ascii_lowercase().as_str() {
+        "any" | "all" => Ok(OriginalFilter::All),
+        "only" => Ok(OriginalFilter::OriginalOnly),
+        "skip" | "compressed" => Ok(OriginalFilter::CompressedOnly),
+        _ => bail!(t!("cli.unknown_original", value)),
+    }
+}
+
+/// 删除前在终端确认；标准输入不是终端时必须使用 --yes
+fn confirm() -> Result<bool> {
+    let stdin = io::stdin();
+    if !stdin.is_terminal() {
+        bail!(t!("cli.confirm_needs_yes"));
+    }
+
+    print!("{}", t!("cli.confirm_clean"));
+    io::stdout().flush()?;
+    let mut answer = String::new();
+    stdin.lock().read_line(&mut answer)?;
+    Ok(matches!(answer.trim().to_ascii_lowercase().as_str(), "y" | "yes"))
+}
+
+/// 不进入 TUI，直接按参数清理
+pub async fn run_clean(
+    args: &CleanArgs,
+    stats: &[GroupStats],
+    checker: &FileChecker,
+    logger: &Logger,
+) -> Result<()> {
+    let wanted: HashSet<&str> = args.groups.iter().map(String::as_str).collect();
+    let known: HashSet<&str> = stats.iter().map(|s| s.group_id.as_str()).collect();
+    let unknown: Vec<&str> = args
+        .groups
+        .iter()
+        .map(String::as_str)
+        .filter(|id| !known.contains(id))
+        .collect();
+    if !unknown.is_empty() {
+        bail!(t!("cli.unknown_groups", unknown.join(", ")));
+    }
+
+    let base_filter = FileFilter::new(args.range)
+        .with_original(args.original)
+        .with_rules(args.rules.clone());
+
+    // 每个参与清理的群实际要删除的文件
+    let filters: HashMap<&str, FileFilter> = match args.budget {
+        Some(budget) => {
+            let pinned: HashSet<String> = args.pinned.iter().cloned().collect();
+            let plan = policy::evict_for_budget(stats, budget, &pinned, &base_filter, args.scope);
+            if plan.usage <= budget {
+                println!("{}", t!("log.budget_met", format_bytes(plan.usage), format_bytes(budget)));
+                return Ok(());
+            }
+            println!(
+                "{}",
+                t!(
+                    "log.budget_selected",
+                    format_bytes(plan.usage),
+                    format_bytes(budget),
+                    plan.evicted,
+                    plan.files.len(),
+                    format_bytes(plan.freed)
+                )
+            );
+            if plan.remaining() > budget {
+                println!("{}", t!("log.budget_unreachable", format_bytes(plan.remaining())));
+            }
+            stats
+                .iter()
+                .filter_map(|stat| {
+                    let keys = plan.files.get(&stat.group_id)?;
+                    Some((stat.group_id.as_str(), base_filter.clone().with_files(keys.clone())))
+                })
+                .collect()
+        }
+        // 保留策略按群计算
+        None => stats
+            .iter()
+            .filter(|s| args.all || wanted.contains(s.group_id.as_str()))
+            .map(|stat| {
+                let filter = match args.policy.select(stat, &base_filter) {
+                    Some(keys) => base_filter.clone().with_files(keys),
+                    None => base_filter.clone(),
+                };
+                (stat.group_id.as_str(), filter)
+            })
+            .collect(),
+    };
+    let targets: Vec<&GroupStats> = stats
+        .iter()
+        .filter(|s| filters.contains_key(s.group_id.as_str()))
+        .collect();
+    let projection = projection::project(stats, |stat| filters.get(stat.group_id.as_str()).cloned());
+
+    println!(
+        "{}",
+        t!(
+            "cli.clean_plan",
+            targets.len(),
+            args.range.description(),
+            args.original.description(),
+            args.scope.description()
+        )
+    );
+    if let Some(summary) = args.rules.summary() {
+        println!("{}{}", t!("ui.file_rules"), summary);
+    }
+    if args.policy != GroupPolicy::None {
+        println!("{}{}", t!("ui.group_policy"), args.policy.description());
+    }
+    for stat in &targets {
+        let filter = &filters[stat.group_id.as_str()];
+        let matched = stat
+            .files
+            .iter()
+            .filter(|f| f.actual_size.is_some() && filter.matches(f))
+            .count();
+        println!("  {} ({}): {}", stat.group_name, stat.group_id, t!("cli.clean_group_files", matched));
+    }
+    let ori_freed = if args.scope.includes_ori() { projection.ori_local } else { 0 };
+    let thumb_freed = if args.scope.includes_thumb() { projection.thumb_local } else { 0 };
+    println!(
+        "{}",
+        t!(
+            "cli.clean_projection",
+            format_bytes(projection.freed(args.scope)),
+            format_bytes(ori_freed),
+            format_bytes(thumb_freed),
+            format_bytes(projection.linked(args.scope)),
+            format_bytes(projection.shared(args.scope))
+        )
+    );
+
+    if args.dry_run {
+        println!("{}", t!("cli.dry_run_done"));
+        return Ok(());
+    }
+
+    // 与 TUI 确认对话框相同的预检查
+    let issues = preflight::run(&PreflightRequest {
+        qq_data_dir: checker.qq_data_dir(),
+        writes_source: true,
+        target_dir: None,
+        required_bytes: 0,
+        writes_back: false,
+    });
+    if !issues.is_empty() {
+        eprintln!("{}", t!("ui.preflight_blocked"));
+        for issue in &issues {
+            eprintln!("  - {}", issue);
+        }
+        let _ = logger.log(LogLevel::Error.label(), &t!("log.preflight_failed", issues.join("; ")));
+        bail!(t!("cli.preflight_failed"));
+    }
+
+    if !args.yes && !confirm()? {
+        println!("{}", t!("cli.clean_cancelled"));
+        return Ok(());
+    }
+
+    let _ = logger.log(
+        LogLevel::Info.label(),
+        &t!("log.clean_start", targets.len(), args.scope.description()),
+    );
+
+    let mut total_deleted = 0;
+    let mut total_failed = 0;
+    for stat in targets {
+        let filter = &filters[stat.group_id.as_str()];
+        match checker.delete_group_files(stat, Some(filter), args.scope).await {
+            Ok((deleted, failed)) => {
+                total_deleted += deleted;
+                total_failed += failed;
+                let message = t!("log.clean_done_group", stat.group_name, deleted);
+                println!("{}", message);
+                let _ = logger.log(LogLevel::Success.label(), &message);
+            }
+            Err(e) => {
+                let message = t!("log.clean_failed", stat.group_name, e);
+                eprintln!("{}", message);
+                let _ = logger.log(LogLevel::Error.label(), &message);
+            }
+        }
+    }
+
+    println!("{}", t!("cli.clean_finished", total_deleted, total_failed));
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_args(args: &[&str]) -> Result<Command> {
+        parse(args.iter().map(|s| s.to_string()))
+    }
+
+    fn clean_args(args: &[&str]) -> CleanArgs {
+        match parse_args(args) {
+            Ok(Command::Clean(args)) => args,
+            Ok(_) => panic!("不是 clean 命令: {:?}", args),
+            Err(e) => panic!("解析失败 {:?}: {}", args, e),
+        }
+    }
+
+    #[test]
+    fn no_arguments_start_the_tui() {
+        assert!(matches!(parse_args(&[]), Ok(Command::Tui)));
+        assert!(matches!(parse_args(&["--help"]), Ok(Command::Help)));
+        assert!(matches!(parse_args(&["clean", "--all", "-h"]), Ok(Command::Help)));
+        assert!(parse_args(&["purge"]).is_err());
+    }
+
+    #[test]
+    fn parses_clean_options() {
+        let args = clean_args(&["clean", "--group", "123, 456", "--group=789", "--scope=ori", "--range", "90d", "-n"]);
+        assert_eq!(args.groups, ["123", "456", "789"]);
+        assert_eq!(args.scope, CleanScope::OriOnly);
+        assert_eq!(args.range, TimeRange::OlderThan(90));
+        assert!(args.dry_run);
+        assert!(!args.yes);
+        assert!(!args.all);
+
+        let args = clean_args(&["clean", "--all", "--scope", "thumb", "-y"]);
+        assert!(args.all);
+        assert_eq!(args.scope, CleanScope::ThumbOnly);
+        assert!(args.yes);
+    }
+
+    #[test]
+    fn rejects_invalid_clean_arguments() {
+        assert!(parse_args(&["clean"]).is_err());
+        assert!(parse_args(&["clean", "--all", "--group", "1"]).is_err());
+        assert!(parse_args(&["clean", "--all", "--scope"]).is_err());
+        assert!(parse_args(&["clean", "--all", "--scope", "everything"]).is_err());
+        assert!(parse_args(&["clean", "--all", "--min-size", "big"]).is_err());
+        assert!(parse_args(&["clean", "--all", "--verbose"]).is_err());
+    }
+
+    #[test]
+    fn parses_file_conditions() {
+        let args = clean_args(&[
+            "clean", "--all", "--original", "skip", "--min-size", "5MB", "--max-size=1G", "--ext", "jpg,.PNG", "--static",
+        ]);
+        assert_eq!(args.original, OriginalFilter::CompressedOnly);
+        assert_eq!(args.rules.min_size, 5 * 1024 * 1024);
+        assert_eq!(args.rules.max_size, Some(1024 * 1024 * 1024));
+        assert_eq!(args.rules.extensions, Some(vec!["jpg".to_string(), "png".to_string()]));
+        assert_eq!(args.rules.animation, AnimationFilter::Static);
+    }
+
+    #[test]
+    fn parses_group_policies() {
+        let args = clean_args(&["clean", "--all", "--keep-newest", "500"]);
+        assert_eq!(args.policy, GroupPolicy::KeepNewest(500));
+
+        let args = clean_args(&["clean", "--group", "1", "--quota=1GB"]);
+        assert_eq!(args.policy, GroupPolicy::Quota(1024 * 1024 * 1024));
+
+        assert!(parse_args(&["clean", "--all", "--keep-newest", "5", "--quota", "1GB"]).is_err());
+        assert!(parse_args(&["clean", "--all", "--keep-newest", "many"]).is_err());
+    }
+
+    #[test]
+    fn parses_budget_and_pins() {
+        let args = clean_args(&["clean", "--budget", "20GB", "--pin", "123,456", "--pin=789"]);
+        assert_eq!(args.budget, Some(20 * 1024 * 1024 * 1024));
+        assert_eq!(args.pinned, vec!["123", "456", "789"]);
+
+        assert!(parse_args(&["clean", "--budget", "20GB", "--group", "123"]).is_err());
+        assert!(parse_args(&["clean", "--budget", "20GB", "--keep-newest", "5"]).is_err());
+        assert!(parse_args(&["clean", "--budget", "lots"]).is_err());
+    }
+}
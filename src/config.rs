@@ -1,5 +1,6 @@
 use anyhow::{Context, Result};
 use serde::Deserialize;
+use std::collections::HashMap;
 use std::env;
 use std::fs;
 use std::path::PathBuf;
@@ -8,6 +9,12 @@ use std::path::PathBuf;
 pub struct Config {
     pub paths: PathsConfig,
     pub database: DatabaseConfig,
+    #[serde(default)]
+    pub locale: Option<String>,  // 界面语言，如 "zh-CN"、"en"；未设置时根据 LANG 选择
+    #[serde(default)]
+    pub keymap: HashMap<String, Vec<String>>,  // 操作名 -> 按键列表，覆盖默认快捷键
+    #[serde(default)]
+    pub pinned_groups: Vec<String>,  // 全局预算淘汰时跳过的群号
 }
 
 #[derive(Debug, Deserialize)]
@@ -37,8 +44,8 @@ impl Config {
 
         if let Some(path) = config_path {
             let content = fs::read_to_string(&path)
-                .with_context(|| format!("无法读取配置文件: {:?}", path))?;
-            let config: Config = toml::from_str(&content).context("配置文件格式错误")?;
+                .with_context(|| t!("error.read_config", path.display()))?;
+            let config: Config = toml::from_str(&content).with_context(|| t!("error.config_format"))?;
             Ok(config)
         } else {
             Ok(Self::default())
@@ -59,11 +66,14 @@ impl Config {
                 files_db_name: "files_in_chat.clean.db".to_string(),
                 group_db_name: "group_info.clean.db".to_string(),
             },
+            locale: None,
+            keymap: HashMap::new(),
+            pinned_groups: Vec::new(),
         }
     }
 
     pub fn get_qq_base_dir(&self) -> Result<PathBuf> {
-        let home_dir = dirs::home_dir().context("无法获取用户主目录")?;
+        let home_dir = dirs::home_dir().context(t!("error.no_home"))?;
         Ok(home_dir.join(&self.paths.qq_data_base))
     }
 
@@ -92,13 +102,13 @@ impl Config {
     #[cfg(not(debug_assertions))]
     fn get_platform_data_dir(&self) -> Result<PathBuf> {
         let data_dir = dirs::data_dir()
-            .context("无法获取平台数据目录")?
+            .context(t!("error.no_data_dir"))?
             .join("qqcleaner")
             .join(&self.database.db_dir);
 
         if !data_dir.exists() {
             fs::create_dir_all(&data_dir)
-                .with_context(|| format!("创建数据目录失败: {:?}", data_dir))?;
+                .with_context(|| t!("error.create_data_dir", data_dir.display()))?;
         }
 
         Ok(data_dir)
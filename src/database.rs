@@ -13,9 +13,9 @@ pub struct Database {
 impl Database {
     pub fn new<P: AsRef<Path>>(files_db: P, group_db: P) -> Result<Self> {
         let files_conn = Connection::open(files_db)
-            .context("无法打开 files_in_chat.clean.db")?;
+            .with_context(|| t!("error.open_db", "files_in_chat.clean.db"))?;
         let group_conn = Connection::open(group_db)
-            .context("无法打开 group_info.clean.db")?;
+            .with_context(|| t!("error.open_db", "group_info.clean.db"))?;
 
         Ok(Database {
             files_conn,
@@ -47,6 +47,10 @@ impl Database {
                 msg_time: row.get(12)?,
                 original: row.get(13).unwrap_or(0),
                 actual_size: None,
+                linked_size: 0,
+                ori_linked_size: 0,
+                ori_size: None,
+                thumb_size: None,
             })
         })?
         .filter_map(|r| r.ok())
@@ -66,7 +70,7 @@ impl Database {
             let group_id: i64 = row.get(0)?;
             Ok(GroupInfo {
                 group_id: group_id.to_string(),
-                group_name: row.get(1).unwrap_or_else(|_| format!("群 {}", group_id)),
+                group_name: row.get(1).unwrap_or_else(|_| t!("models.unnamed_group", group_id)),
                 group_remark: row.get(2).ok(),
                 owner_uid: row.get(3).unwrap_or_default(),
                 create_time: row.get(4).unwrap_or(0),
@@ -12,10 +12,10 @@ pub struct Decryptor {
 impl Decryptor {
     pub fn new() -> Result<Self> {
         let key_path = Self::find_key_file()
-            .context("未找到 sqlcipher.key 文件")?;
+            .context(t!("error.key_missing"))?;
 
         register_offset_vfs()
-            .map_err(|e| anyhow::anyhow!("注册 offset VFS 失败: {:?}", e))?;
+            .map_err(|e| anyhow::anyhow!(t!("error.register_vfs", format!("{:?}", e))))?;
 
         Ok(Decryptor { key_path })
     }
@@ -33,7 +33,7 @@ impl Decryptor {
 
     fn read_key(&self) -> Result<String> {
         let key = fs::read_to_string(&self.key_path)
-            .with_context(|| format!("无法读取密钥文件: {:?}", self.key_path))?;
+            .with_context(|| t!("error.read_key", self.key_path.display()))?;
 
         Ok(key.trim().to_string())
     }
@@ -43,7 +43,7 @@ impl Decryptor {
         let output_path = output_db.as_ref();
 
         if !encrypted_path.exists() {
-            bail!("加密数据库不存在: {:?}", encrypted_path);
+            bail!(t!("error.encrypted_db_missing", encrypted_path.display()));
         }
 
         let key = self.read_key()?;
@@ -52,7 +52,7 @@ impl Decryptor {
             encrypted_path.display(),
             OFFSET_VFS_NAME
         ))
-        .with_context(|| format!("打开加密数据库失败: {:?}", encrypted_path))?;
+        .with_context(|| t!("error.open_encrypted_db", encrypted_path.display()))?;
 
         let decrypt_info = DBDecryptInfo {
             key,
@@ -60,10 +60,10 @@ impl Decryptor {
         };
 
         try_decrypt_db(&conn, decrypt_info)
-            .map_err(|e| anyhow::anyhow!("解密失败: {:?}", e))?;
+            .map_err(|e| anyhow::anyhow!(t!("error.decrypt", format!("{:?}", e))))?;
 
         export_to_plain(&conn, output_path)
-            .map_err(|e| anyhow::anyhow!("导出普通数据库失败: {:?}", e))?;
+            .map_err(|e| anyhow::anyhow!(t!("error.export", format!("{:?}", e))))?;
 
         Ok(())
     }
@@ -78,12 +78,12 @@ impl Decryptor {
         let output_path = output_dir.as_ref();
 
         if !nt_db_path.exists() {
-            bail!("nt_db 目录不存在: {:?}", nt_db_path);
+            bail!(t!("error.nt_db_missing", nt_db_path.display()));
         }
 
         if !output_path.exists() {
             fs::create_dir_all(output_path)
-                .with_context(|| format!("创建输出目录失败: {:?}", output_path))?;
+                .with_context(|| t!("error.create_output_dir", output_path.display()))?;
         }
 
         for db_name in db_names {
@@ -92,12 +92,12 @@ impl Decryptor {
                 db_name.trim_end_matches(".db")));
 
             if encrypted_db.exists() {
-                println!("正在解密: {}", db_name);
+                println!("{}", t!("cli.decrypting_db", db_name));
                 self.decrypt_database(&encrypted_db, &output_db)
-                    .with_context(|| format!("解密 {} 失败", db_name))?;
-                println!("✓ 解密成功: {}", db_name);
+                    .with_context(|| t!("error.decrypt_db", db_name))?;
+                println!("{}", t!("cli.decrypted_db", db_name));
             } else {
-                println!("跳过不存在的数据库: {}", db_name);
+                println!("{}", t!("cli.skip_missing_db", db_name));
             }
         }
 
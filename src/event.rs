@@ -1,9 +1,15 @@
 use anyhow::Result;
-use crossterm::event::{self, Event, KeyCode, KeyEvent, KeyModifiers};
+use crossterm::event::{
+    self, Event, KeyCode, KeyEvent, KeyModifiers, MouseButton, MouseEvent, MouseEventKind,
+};
+use ratatui::layout::{Position, Rect};
+
+use crate::keymap::{Action, KeyContext};
 use std::time::Duration;
 
 pub enum AppEvent {
     Key(KeyEvent),
+    Mouse(MouseEvent),
     Tick,
 }
 
@@ -18,6 +24,7 @@ impl EventHandler {
         if event::poll(Duration::from_millis(100))? {
             match event::read()? {
                 Event::Key(key) => Ok(AppEvent::Key(key)),
+                Event::Mouse(mouse) => Ok(AppEvent::Mouse(mouse)),
                 _ => Ok(AppEvent::Tick),
             }
         } else {
@@ -35,39 +42,29 @@ pub fn handle_key_event(app: &mut crate::app::App, key: KeyEvent) {
     }
 
     if app.show_help {
-        match key.code {
-            KeyCode::Esc | KeyCode::Char('?') | KeyCode::Char('h') => {
-                app.toggle_help();
-            }
-            _ => {}
+        if key.code == KeyCode::Esc || app.keymap.action(&key, KeyContext::Global) == Some(Action::Help) {
+            app.toggle_help();
         }
         return;
     }
 
     if app.show_confirm_dialog {
-        match key.code {
-            KeyCode::Char('y') | KeyCode::Char('Y') | KeyCode::Enter => {
-                app.show_confirm_dialog = false;
-            }
-            KeyCode::Char('n') | KeyCode::Char('N') | KeyCode::Esc => {
-                app.hide_confirm();
-            }
-            KeyCode::Char(' ') => {
+        let path_action = matches!(app.confirm_action, Some(ConfirmAction::Migrate | ConfirmAction::Restore));
+        match app.keymap.action(&key, KeyContext::Confirm) {
+            Some(Action::Confirm) => accept_confirm(app),
+            Some(Action::Cancel) => app.hide_confirm(),
+            Some(Action::Toggle) => {
                 if matches!(app.confirm_action, Some(ConfirmAction::Migrate)) {
                     app.toggle_confirm_migrate_option();
                 }
             }
-            KeyCode::Left => {
-                if matches!(app.confirm_action, Some(ConfirmAction::Migrate)) {
-                    app.prev_migrate_path();
-                    app.add_log(crate::app::LogLevel::Info, &format!("迁移路径: {}", app.migrate_target_path.display()));
-                }
+            Some(Action::PrevPath) if path_action => {
+                app.prev_migrate_path();
+                log_migrate_path(app);
             }
-            KeyCode::Right | KeyCode::Char('p') => {
-                if matches!(app.confirm_action, Some(ConfirmAction::Migrate)) {
-                    app.next_migrate_path();
-                    app.add_log(crate::app::LogLevel::Info, &format!("迁移路径: {}", app.migrate_target_path.display()));
-                }
+            Some(Action::NextPath) if path_action => {
+                app.next_migrate_path();
+                log_migrate_path(app);
             }
             _ => {}
         }
@@ -75,147 +72,339 @@ pub fn handle_key_event(app: &mut crate::app::App, key: KeyEvent) {
     }
 
     if app.show_filter_dialog {
-        match key.code {
-            KeyCode::Up | KeyCode::Char('k') => {
-                app.filter_prev_item();
-            }
-            KeyCode::Down | KeyCode::Char('j') => {
-                app.filter_next_item();
-            }
-            KeyCode::Char(' ') | KeyCode::Enter => {
-                app.toggle_filter_option();
-            }
-            KeyCode::Char('a') => {
-                app.apply_filter_dialog();
-            }
-            KeyCode::Char('c') | KeyCode::Esc => {
-                app.cancel_filter_dialog();
+        if app.filter_input.is_some() {
+            match key.code {
+                KeyCode::Esc => app.cancel_filter_input(),
+                KeyCode::Enter => app.submit_filter_input(),
+                KeyCode::Backspace => app.filter_input_pop(),
+                KeyCode::Char(c) => app.filter_input_push(c),
+                _ => {}
             }
+            return;
+        }
+        match app.keymap.action(&key, KeyContext::Filter) {
+            Some(Action::Up) => app.filter_prev_item(),
+            Some(Action::Down) => app.filter_next_item(),
+            Some(Action::Toggle | Action::Open) => app.toggle_filter_option(),
+            Some(Action::Apply) => app.apply_filter_dialog(),
+            Some(Action::Cancel) => app.cancel_filter_dialog(),
             _ => {}
         }
         return;
     }
 
-    match key.code {
-        KeyCode::Char('q') => {
-            app.quit();
+    if app.search_active {
+        match key.code {
+            KeyCode::Esc => app.clear_search(),
+            KeyCode::Enter | KeyCode::Down | KeyCode::Up => app.finish_search(),
+            KeyCode::Backspace => app.search_pop(),
+            KeyCode::Char(c) => app.search_push(c),
+            _ => {}
         }
-        KeyCode::Char('?') | KeyCode::Char('h') => {
-            app.toggle_help();
+        return;
+    }
+
+    if app.time_input.is_some() {
+        match key.code {
+            KeyCode::Esc => app.cancel_time_input(),
+            KeyCode::Enter => app.submit_time_input(),
+            KeyCode::Backspace => app.time_input_pop(),
+            KeyCode::Char(c) => app.time_input_push(c),
+            _ => {}
         }
-        KeyCode::Tab => {
-            app.next_tab();
+        return;
+    }
+
+    if app.budget_input.is_some() {
+        match key.code {
+            KeyCode::Esc => app.cancel_budget_input(),
+            KeyCode::Enter => app.submit_budget_input(),
+            KeyCode::Backspace => app.budget_input_pop(),
+            KeyCode::Char(c) => app.budget_input_push(c),
+            _ => {}
         }
-        KeyCode::BackTab => {
-            app.prev_tab();
+        return;
+    }
+
+    if let Some(action) = app.keymap.action(&key, KeyContext::Global) {
+        match action {
+            Action::Quit => app.quit(),
+            Action::Help => app.toggle_help(),
+            Action::NextTab => app.next_tab(),
+            Action::PrevTab => app.prev_tab(),
+            Action::TabAnalysis => app.set_tab(AppTab::Analysis),
+            Action::TabClean => app.set_tab(AppTab::Clean),
+            Action::TabMigrate => app.set_tab(AppTab::Migrate),
+            Action::TabLogs => app.set_tab(AppTab::Logs),
+            Action::TimeRange => app.set_time_range(app.time_range.next_preset()),
+            Action::TimeRangeInput => app.start_time_input(),
+            Action::OriginalFilter => app.cycle_original_filter(),
+            Action::GroupPolicy => app.cycle_group_policy(),
+            _ => {}
         }
-        KeyCode::Char('1') => app.current_tab = AppTab::Analysis,
-        KeyCode::Char('2') => app.current_tab = AppTab::Clean,
-        KeyCode::Char('3') => app.current_tab = AppTab::Migrate,
-        _ => {}
+        return;
     }
 
     match app.current_tab {
-        AppTab::Clean | AppTab::Migrate => {
-            match key.code {
-                KeyCode::Down | KeyCode::Char('j') => {
-                    app.next_item();
-                    return;
-                }
-                KeyCode::Up | KeyCode::Char('k') => {
-                    app.prev_item();
-                    return;
-                }
-                KeyCode::Char(' ') => {
-                    app.toggle_selected_group();
-                    return;
-                }
-                KeyCode::Char('a') => {
-                    app.select_all_filtered();
-                    return;
-                }
-                KeyCode::Char('A') => {
-                    app.deselect_all();
-                    return;
-                }
+        AppTab::Logs => {
+            match app.keymap.action(&key, KeyContext::Logs) {
+                Some(Action::Up) => app.scroll_logs_up(1),
+                Some(Action::Down) => app.scroll_logs_down(1),
+                Some(Action::PageUp) => app.scroll_logs_up(10),
+                Some(Action::PageDown) => app.scroll_logs_down(10),
+                Some(Action::Top) => app.scroll_logs_up(usize::MAX / 2),
+                Some(Action::Bottom) => app.log_scroll = 0,
+                Some(Action::LogFilter) => app.cycle_log_filter(),
                 _ => {}
             }
+            return;
         }
-        _ => {}
-    }
-
-    match key.code {
-        KeyCode::Char('t') => {
-            app.time_range = match app.time_range {
-                crate::time_range::TimeRange::All => crate::time_range::TimeRange::DaysAgo(7),
-                crate::time_range::TimeRange::DaysAgo(7) => crate::time_range::TimeRange::DaysAgo(30),
-                crate::time_range::TimeRange::DaysAgo(30) => crate::time_range::TimeRange::DaysAgo(90),
-                crate::time_range::TimeRange::DaysAgo(90) => crate::time_range::TimeRange::DaysAgo(180),
-                crate::time_range::TimeRange::DaysAgo(_) => crate::time_range::TimeRange::All,
-            };
-            app.add_log(crate::app::LogLevel::Info, &format!("时间范围: {}", app.time_range.description()));
+        AppTab::Analysis => {
+            match app.keymap.action(&key, KeyContext::Analysis) {
+                Some(Action::ChartOlder) => app.scroll_chart_older(1),
+                Some(Action::ChartNewer) => app.scroll_chart_newer(1),
+                Some(Action::ChartMetric) => app.cycle_chart_metric(),
+                Some(Action::ChartSelected) => app.toggle_chart_selected(),
+                Some(Action::ChartView) => app.toggle_chart_view(),
+                Some(Action::Down) => app.treemap_next(),
+                Some(Action::Up) => app.treemap_prev(),
+                Some(Action::Open) => app.treemap_open(),
+                Some(Action::Back) => app.treemap_back(),
+                Some(Action::JumpToGroup) => app.jump_to_treemap_group(),
+                _ => {}
+            }
             return;
         }
-        _ => {}
+        AppTab::Clean | AppTab::Migrate => {}
     }
 
-    match app.current_tab {
-        AppTab::Clean | AppTab::Migrate => {
-            match key.code {
-                KeyCode::Char('s') => {
-                    app.sort_by = match app.sort_by {
-                        crate::app::SortBy::Size => crate::app::SortBy::FileCount,
-                        crate::app::SortBy::FileCount => crate::app::SortBy::Name,
-                        crate::app::SortBy::Name => crate::app::SortBy::Size,
-                    };
-                    app.apply_sort();
-                    app.add_log(crate::app::LogLevel::Info, &format!("排序方式: {:?}", app.sort_by));
-                }
-                KeyCode::Char('f') => {
-                    app.open_filter_dialog();
-                }
-                _ => {}
-            }
+    if app.file_view.is_some() {
+        match app.keymap.action(&key, KeyContext::FileList) {
+            Some(Action::Back) => app.close_file_view(),
+            Some(Action::Down) => app.file_view_next(),
+            Some(Action::Up) => app.file_view_prev(),
+            Some(Action::Toggle) => app.toggle_selected_file(),
+            Some(Action::SelectAll) => app.select_all_files(),
+            Some(Action::DeselectAll) => app.clear_selected_files(),
+            Some(Action::Sort) => app.cycle_file_sort(),
+            Some(Action::ReverseSort) => app.toggle_file_sort_direction(),
+            Some(Action::Preview) => app.toggle_preview(),
+            _ => handle_tab_action(app, &key),
+        }
+    } else {
+        match app.keymap.action(&key, KeyContext::GroupList) {
+            Some(Action::Open) => app.open_file_view(),
+            Some(Action::Search) => app.start_search(),
+            Some(Action::Back) if !app.search_query.is_empty() => app.clear_search(),
+            Some(Action::Down) => app.next_item(),
+            Some(Action::Up) => app.prev_item(),
+            Some(Action::Toggle) => app.toggle_selected_group(),
+            Some(Action::SelectAll) => app.select_all_filtered(),
+            Some(Action::DeselectAll) => app.deselect_all(),
+            Some(Action::SelectQuit) => app.select_quit_groups(),
+            Some(Action::Pin) => app.toggle_pin(),
+            Some(Action::Budget) => app.start_budget_input(),
+            Some(Action::Sort) => app.cycle_sort(),
+            Some(Action::ReverseSort) => app.toggle_sort_direction(),
+            Some(Action::Filter) => app.open_filter_dialog(),
+            Some(Action::Details) => app.toggle_details(),
+            _ => handle_tab_action(app, &key),
         }
-        _ => {}
     }
+}
+
+/// 清理页和迁移页各自的操作按键
+fn handle_tab_action(app: &mut crate::app::App, key: &KeyEvent) {
+    use crate::app::{AppTab, ConfirmAction, LogLevel};
 
     if app.current_tab == AppTab::Clean {
-        match key.code {
-            KeyCode::Char('d') | KeyCode::Delete => {
+        match app.keymap.action(key, KeyContext::Clean) {
+            Some(Action::Clean) => {
                 if app.selected_count() > 0 {
                     app.show_confirm(ConfirmAction::Clean);
                 } else {
-                    app.add_log(crate::app::LogLevel::Warning, "请先选择要清理的群组");
+                    app.add_log(LogLevel::Warning, t!("log.select_to_clean"));
                 }
             }
+            Some(Action::CleanScope) => app.cycle_clean_scope(),
             _ => {}
         }
+        return;
     }
 
-    if app.current_tab == AppTab::Migrate {
-        match key.code {
-            KeyCode::Char('m') | KeyCode::Enter => {
-                if app.selected_count() > 0 {
-                    app.show_confirm(ConfirmAction::Migrate);
-                } else {
-                    app.add_log(crate::app::LogLevel::Warning, "请先选择要迁移的群组");
-                }
+    match app.keymap.action(key, KeyContext::Migrate) {
+        Some(Action::Migrate) => {
+            if app.selected_count() > 0 {
+                app.show_confirm(ConfirmAction::Migrate);
+            } else {
+                app.add_log(LogLevel::Warning, t!("log.select_to_migrate"));
             }
-            KeyCode::Char('p') => {
-                app.next_migrate_path();
-                app.add_log(crate::app::LogLevel::Info, &format!("迁移路径: {}", app.migrate_target_path.display()));
+        }
+        Some(Action::Doctor) => app.request_doctor(),
+        Some(Action::Restore) => {
+            if app.selected_count() > 0 {
+                app.show_confirm(ConfirmAction::Restore);
+            } else {
+                app.add_log(LogLevel::Warning, t!("log.select_to_restore"));
             }
-            KeyCode::Left => {
-                app.prev_migrate_path();
-                app.add_log(crate::app::LogLevel::Info, &format!("迁移路径: {}", app.migrate_target_path.display()));
+        }
+        Some(Action::NextPath) => {
+            app.next_migrate_path();
+            log_migrate_path(app);
+        }
+        Some(Action::PrevPath) => {
+            app.prev_migrate_path();
+            log_migrate_path(app);
+        }
+        _ => {}
+    }
+}
+
+fn log_migrate_path(app: &mut crate::app::App) {
+    let message = t!("log.migrate_path", app.migrate_target_path.display());
+    app.add_log(crate::app::LogLevel::Info, &message);
+}
+
+/// 确认对话框中的"确认"：预检查通过后关闭对话框，由主循环执行操作
+fn accept_confirm(app: &mut crate::app::App) {
+    app.refresh_preflight();
+    if app.preflight_issues.is_empty() {
+        app.show_confirm_dialog = false;
+    } else {
+        for issue in app.preflight_issues.clone() {
+            app.add_log(crate::app::LogLevel::Warning, &t!("log.preflight_failed", issue));
+        }
+    }
+}
+
+/// 处理鼠标事件，`area` 为整个终端区域，用于和界面布局做命中测试
+pub fn handle_mouse_event(app: &mut crate::app::App, mouse: MouseEvent, area: Rect) {
+    // 输入搜索词、时间范围或预算时与按键处理一样是模态的，点击和滚动都不生效
+    if app.search_active || app.time_input.is_some() || app.budget_input.is_some() {
+        return;
+    }
+
+    match mouse.kind {
+        MouseEventKind::ScrollDown => scroll(app, true),
+        MouseEventKind::ScrollUp => scroll(app, false),
+        MouseEventKind::Down(MouseButton::Left) => click(app, mouse.column, mouse.row, area),
+        MouseEventKind::Drag(MouseButton::Left)
+            if !app.show_help && !app.show_confirm_dialog && !app.show_filter_dialog =>
+        {
+            if let Some((index, _)) = crate::ui::list_row_at(area, app, mouse.column, mouse.row) {
+                highlight_row(app, index);
             }
-            KeyCode::Right => {
-                app.next_migrate_path();
-                app.add_log(crate::app::LogLevel::Info, &format!("迁移路径: {}", app.migrate_target_path.display()));
+        }
+        _ => {}
+    }
+}
+
+fn scroll(app: &mut crate::app::App, down: bool) {
+    use crate::app::AppTab;
+
+    if app.show_help || app.show_confirm_dialog {
+        return;
+    }
+
+    if app.show_filter_dialog {
+        if app.filter_input.is_some() {
+            return;
+        }
+        if down {
+            app.filter_next_item();
+        } else {
+            app.filter_prev_item();
+        }
+        return;
+    }
+
+    if app.current_tab == AppTab::Logs {
+        if down {
+            app.scroll_logs_down(3);
+        } else {
+            app.scroll_logs_up(3);
+        }
+        return;
+    }
+
+    if app.current_tab == AppTab::Analysis {
+        if down {
+            app.scroll_chart_newer(1);
+        } else {
+            app.scroll_chart_older(1);
+        }
+        return;
+    }
+
+    match (app.file_view.is_some(), down) {
+        (true, true) => app.file_view_next(),
+        (true, false) => app.file_view_prev(),
+        (false, true) => app.next_item(),
+        (false, false) => app.prev_item(),
+    }
+}
+
+fn click(app: &mut crate::app::App, column: u16, row: u16, area: Rect) {
+    use crate::app::AppTab;
+
+    let position = Position::new(column, row);
+
+    if app.show_help {
+        app.toggle_help();
+        return;
+    }
+
+    if app.show_confirm_dialog {
+        let buttons = crate::ui::confirm_buttons(area, app);
+        if buttons[0].contains(position) {
+            accept_confirm(app);
+        } else if buttons[1].contains(position) {
+            app.hide_confirm();
+        }
+        return;
+    }
+
+    if app.show_filter_dialog {
+        // 点击任意位置先提交正在编辑的输入，输入有误时停留在编辑状态
+        if app.filter_input.is_some() {
+            app.submit_filter_input();
+            if app.filter_input.is_some() {
+                return;
+            }
+        }
+        let buttons = crate::ui::filter_buttons(area, app);
+        if buttons[0].contains(position) {
+            app.apply_filter_dialog();
+        } else if buttons[1].contains(position) {
+            app.cancel_filter_dialog();
+        } else if let Some(option) = crate::ui::filter_option_at(area, app, row) {
+            app.filter_cursor = option;
+            app.toggle_filter_option();
+        }
+        return;
+    }
+
+    if let Some(tab) = crate::ui::tab_at(area, column, row) {
+        app.set_tab(AppTab::from_index(tab));
+        return;
+    }
+
+    // 点击行只移动光标，点击复选框列同时切换选择
+    if let Some((index, on_checkbox)) = crate::ui::list_row_at(area, app, column, row) {
+        highlight_row(app, index);
+        if on_checkbox {
+            if app.file_view.is_some() {
+                app.toggle_selected_file();
+            } else {
+                app.toggle_selected_group();
             }
-            _ => {}
         }
     }
 }
 
+fn highlight_row(app: &mut crate::app::App, index: usize) {
+    match app.file_view.as_mut() {
+        Some(view) => view.selected_index = index,
+        None => app.selected_index = index,
+    }
+}
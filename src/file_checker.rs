@@ -1,10 +1,10 @@
 use anyhow::Result;
-use chrono::{DateTime, Datelike, Utc};
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
 use tokio::fs;
 use tokio::task::JoinSet;
 
-use crate::models::{FileInfo, GroupInfo, GroupStats};
+use crate::file_filter::FileFilter;
+use crate::models::{month_dir, CleanScope, FileInfo, GroupInfo, GroupStats};
 
 pub struct FileChecker {
     qq_data_dir: PathBuf,
@@ -15,7 +15,11 @@ impl FileChecker {
         FileChecker { qq_data_dir }
     }
 
-    fn get_thumb_filenames(filename: &str) -> Vec<String> {
+    pub fn qq_data_dir(&self) -> &Path {
+        &self.qq_data_dir
+    }
+
+    pub fn get_thumb_filenames(filename: &str) -> Vec<String> {
         if let Some(dot_pos) = filename.rfind('.') {
             let name_without_ext = &filename[..dot_pos];
             let ext = &filename[dot_pos..];
@@ -31,6 +35,18 @@ impl FileChecker {
         }
     }
 
+    /// 返回文件的 (本地大小, 链接大小)，失效的符号链接不计入任何一方
+    async fn file_sizes(path: &Path) -> (u64, u64) {
+        match fs::symlink_metadata(path).await {
+            Ok(metadata) if metadata.file_type().is_symlink() => match fs::metadata(path).await {
+                Ok(target) => (0, target.len()),
+                Err(_) => (0, 0),
+            },
+            Ok(metadata) => (metadata.len(), 0),
+            Err(_) => (0, 0),
+        }
+    }
+
     pub async fn check_files_exist_with_size(&self, files: &[FileInfo]) -> Result<Vec<FileInfo>> {
         let mut join_set = JoinSet::new();
 
@@ -48,27 +64,30 @@ impl FileChecker {
                     return file_info;
                 }
 
-                let datetime = DateTime::<Utc>::from_timestamp(msg_time, 0)
-                    .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap());
-
-                let time_dir = format!("{}-{:02}", datetime.year(), datetime.month());
-                let base_dir = qq_data_dir.join(&time_dir);
+                let base_dir = qq_data_dir.join(month_dir(msg_time));
 
-                let mut total_size = 0u64;
+                let mut linked_size = 0u64;
 
                 let ori_path = base_dir.join("Ori").join(&filename);
-                if let Ok(metadata) = fs::metadata(&ori_path).await {
-                    total_size += metadata.len();
-                }
+                let (local, linked) = Self::file_sizes(&ori_path).await;
+                let ori_size = local + linked;
+                let ori_linked = linked;
+                linked_size += linked;
 
+                let mut thumb_size = 0u64;
                 let thumb_filenames = Self::get_thumb_filenames(&filename);
                 for thumb_name in thumb_filenames {
                     let thumb_path = base_dir.join("Thumb").join(&thumb_name);
-                    if let Ok(metadata) = fs::metadata(&thumb_path).await {
-                        total_size += metadata.len();
-                    }
+                    let (local, linked) = Self::file_sizes(&thumb_path).await;
+                    thumb_size += local + linked;
+                    linked_size += linked;
                 }
 
+                let total_size = ori_size + thumb_size;
+                file_info.linked_size = linked_size;
+                file_info.ori_linked_size = ori_linked;
+                file_info.ori_size = (ori_size > 0).then_some(ori_size);
+                file_info.thumb_size = (thumb_size > 0).then_some(thumb_size);
                 file_info.actual_size = if total_size > 0 {
                     Some(total_size)
                 } else {
@@ -104,19 +123,22 @@ impl FileChecker {
             let total_size: u64 = updated_files.iter()
                 .filter_map(|f| f.actual_size)
                 .sum();
+            let linked_size: u64 = updated_files.iter().map(|f| f.linked_size).sum();
 
             let group_name = groups.get(&group_id)
                 .map(|g| g.group_name.clone())
-                .unwrap_or_else(|| format!("群 {}", group_id));
+                .unwrap_or_else(|| t!("models.unnamed_group", group_id));
 
             stats_list.push(GroupStats {
-                group_id,
+                group_id: group_id.clone(),
                 group_name,
                 total_size,
+                linked_size,
                 file_count: updated_files.len(),
                 exist_count,
                 missing_count,
                 files: updated_files,
+                info: groups.get(&group_id).cloned(),
             });
         }
 
@@ -125,18 +147,25 @@ impl FileChecker {
         Ok(stats_list)
     }
 
+    /// 删除群组中符合条件的文件，`scope` 决定删除原图、缩略图还是两者
     pub async fn delete_group_files(
         &self,
         stats: &GroupStats,
-        time_range: Option<&crate::time_range::TimeRange>,
+        filter: Option<&FileFilter>,
+        scope: CleanScope,
     ) -> Result<(usize, usize)> {
         let mut join_set = JoinSet::new();
 
         for file in &stats.files {
+            if let Some(filter) = filter {
+                if !filter.matches(file) {
+                    continue;
+                }
+            }
+
             let filename = file.file_name.clone();
             let qq_data_dir = self.qq_data_dir.clone();
             let msg_time = file.msg_time;
-            let time_range = time_range.cloned();
 
             join_set.spawn(async move {
                 let mut deleted = 0;
@@ -146,33 +175,27 @@ impl FileChecker {
                     return (deleted, failed);
                 }
 
-                if let Some(ref range) = time_range {
-                    if !range.should_delete(msg_time) {
-                        return (deleted, failed);
-                    }
-                }
-
-                let datetime = DateTime::<Utc>::from_timestamp(msg_time, 0)
-                    .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap());
+                let base_dir = qq_data_dir.join(month_dir(msg_time));
 
-                let time_dir = format!("{}-{:02}", datetime.year(), datetime.month());
-                let base_dir = qq_data_dir.join(&time_dir);
-
-                let ori_path = base_dir.join("Ori").join(&filename);
-                match fs::remove_file(&ori_path).await {
-                    Ok(_) => deleted += 1,
-                    Err(_) => {}
-                }
-
-                let thumb_filenames = Self::get_thumb_filenames(&filename);
-                for thumb_name in thumb_filenames {
-                    let thumb_path = base_dir.join("Thumb").join(&thumb_name);
-                    match fs::remove_file(&thumb_path).await {
+                if scope.includes_ori() {
+                    let ori_path = base_dir.join("Ori").join(&filename);
+                    match fs::remove_file(&ori_path).await {
                         Ok(_) => deleted += 1,
                         Err(_) => {}
                     }
                 }
 
+                if scope.includes_thumb() {
+                    let thumb_filenames = Self::get_thumb_filenames(&filename);
+                    for thumb_name in thumb_filenames {
+                        let thumb_path = base_dir.join("Thumb").join(&thumb_name);
+                        match fs::remove_file(&thumb_path).await {
+                            Ok(_) => deleted += 1,
+                            Err(_) => {}
+                        }
+                    }
+                }
+
                 (deleted, failed)
             });
         }
@@ -200,10 +223,41 @@ impl FileChecker {
             .sum();
 
         stats.total_size = total_size;
+        stats.linked_size = updated_files.iter().map(|f| f.linked_size).sum();
         stats.exist_count = exist_count;
         stats.missing_count = missing_count;
         stats.files = updated_files;
 
         Ok(())
     }
+
+    /// 查找 QQ 数据目录中目标已不存在的符号链接（通常是迁移目标所在的驱动器未挂载）
+    pub async fn find_dangling_links(&self) -> Result<Vec<(PathBuf, PathBuf)>> {
+        let mut dangling = Vec::new();
+        let mut pending = vec![self.qq_data_dir.clone()];
+
+        while let Some(dir) = pending.pop() {
+            let mut entries = match fs::read_dir(&dir).await {
+                Ok(entries) => entries,
+                Err(_) => continue,
+            };
+
+            while let Some(entry) = entries.next_entry().await? {
+                let path = entry.path();
+                let file_type = entry.file_type().await?;
+
+                if file_type.is_symlink() {
+                    if fs::metadata(&path).await.is_err() {
+                        let target = fs::read_link(&path).await.unwrap_or_default();
+                        dangling.push((path, target));
+                    }
+                } else if file_type.is_dir() {
+                    pending.push(path);
+                }
+            }
+        }
+
+        dangling.sort();
+        Ok(dangling)
+    }
 }
@@ -0,0 +1,293 @@
+use std::collections::HashSet;
+
+use crate::models::{format_bytes, FileInfo};
+use crate::time_range::TimeRange;
+
+/// 单个文件的筛选条件
+///
+/// 清理、迁移和界面上的"范围内"统计都通过它判断文件是否参与操作，
+/// 新增的逐文件条件只需要加在这里。
+#[derive(Debug, Clone)]
+pub struct FileFilter {
+    pub time_range: TimeRange,
+    pub original: OriginalFilter,
+    pub rules: FileRules,
+    pub only_files: Option<HashSet<String>>, // 在文件列表中手动选择的文件（FileInfo::key）
+}
+
+/// 逐文件的大小、扩展名和动图条件，默认不限制
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct FileRules {
+    pub min_size: u64,                   // 0 表示不限
+    pub max_size: Option<u64>,
+    pub extensions: Option<Vec<String>>, // 小写、不带点，如 ["jpg", "png"]
+    pub animation: AnimationFilter,
+}
+
+/// 动图与静态图片
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub enum AnimationFilter {
+    #[default]
+    All,
+    Animated, // 只处理 GIF
+    Static,   // 只处理非 GIF 图片
+}
+
+impl AnimationFilter {
+    pub fn next(self) -> Self {
+        match self {
+            AnimationFilter::All => AnimationFilter::Animated,
+            AnimationFilter::Animated => AnimationFilter::Static,
+            AnimationFilter::Static => AnimationFilter::All,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            AnimationFilter::All => t!("animation_filter.all"),
+            AnimationFilter::Animated => t!("animation_filter.animated"),
+            AnimationFilter::Static => t!("animation_filter.static"),
+        }
+    }
+}
+
+impl FileRules {
+    /// 已启用条件的简短说明，未设置任何条件时返回 None
+    pub fn summary(&self) -> Option<String> {
+        let mut parts = Vec::new();
+        if self.min_size > 0 {
+            parts.push(format!("≥{}", format_bytes(self.min_size)));
+        }
+        if let Some(max) = self.max_size {
+            parts.push(format!("≤{}", format_bytes(max)));
+        }
+        if let Some(ref extensions) = self.extensions {
+            parts.push(extensions.join("/"));
+        }
+        if self.animation != AnimationFilter::All {
+            parts.push(self.animation.description().to_string());
+        }
+        (!parts.is_empty()).then(|| parts.join(", "))
+    }
+
+    pub fn matches(&self, file: &FileInfo) -> bool {
+        // 已清理的文件没有实际大小，按数据库记录的大小判断
+        let size = file.actual_size.unwrap_or(file.file_size.max(0) as u64);
+        if size < self.min_size {
+            return false;
+        }
+        if self.max_size.is_some_and(|max| size > max) {
+            return false;
+        }
+
+        if let Some(ref extensions) = self.extensions {
+            let matched = file
+                .extension()
+                .is_some_and(|ext| extensions.iter().any(|e| e.eq_ignore_ascii_case(ext)));
+            if !matched {
+                return false;
+            }
+        }
+
+        match self.animation {
+            AnimationFilter::All => true,
+            AnimationFilter::Animated => file.is_gif(),
+            AnimationFilter::Static => !file.is_gif(),
+        }
+    }
+}
+
+/// 解析逗号或空格分隔的扩展名列表，如 "jpg, .png gif"，留空表示不限
+pub fn parse_extensions(input: &str) -> Option<Vec<String>> {
+    let extensions: Vec<String> = input
+        .split([',', ' ', '，'])
+        .map(|ext| ext.trim().trim_start_matches('.').to_ascii_lowercase())
+        .filter(|ext| !ext.is_empty())
+        .collect();
+    (!extensions.is_empty()).then_some(extensions)
+}
+
+/// 按发送时是否勾选"原图"（82302）筛选
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum OriginalFilter {
+    All,
+    OriginalOnly,   // 只处理原图画质的图片
+    CompressedOnly, // 只处理压缩过的图片，保护原图画质的照片
+}
+
+impl OriginalFilter {
+    pub fn next(self) -> Self {
+        match self {
+            OriginalFilter::All => OriginalFilter::CompressedOnly,
+            OriginalFilter::CompressedOnly => OriginalFilter::OriginalOnly,
+            OriginalFilter::OriginalOnly => OriginalFilter::All,
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            OriginalFilter::All => t!("original_filter.all"),
+            OriginalFilter::OriginalOnly => t!("original_filter.original_only"),
+            OriginalFilter::CompressedOnly => t!("original_filter.compressed_only"),
+        }
+    }
+
+    pub fn matches(&self, file: &FileInfo) -> bool {
+        match self {
+            OriginalFilter::All => true,
+            OriginalFilter::OriginalOnly => file.is_original(),
+            OriginalFilter::CompressedOnly => !file.is_original(),
+        }
+    }
+}
+
+impl FileFilter {
+    pub fn new(time_range: TimeRange) -> Self {
+        Self {
+            time_range,
+            original: OriginalFilter::All,
+            rules: FileRules::default(),
+            only_files: None,
+        }
+    }
+
+    pub fn with_original(mut self, original: OriginalFilter) -> Self {
+        self.original = original;
+        self
+    }
+
+    pub fn with_rules(mut self, rules: FileRules) -> Self {
+        self.rules = rules;
+        self
+    }
+
+    /// 限定为手动选择的文件，此时不再按其他条件筛选
+    pub fn with_files(mut self, keys: HashSet<String>) -> Self {
+        self.only_files = Some(keys);
+        self
+    }
+
+    pub fn matches(&self, file: &FileInfo) -> bool {
+        if let Some(ref keys) = self.only_files {
+            return keys.contains(&file.key());
+        }
+
+        self.time_range.should_delete(file.msg_time)
+            && self.original.matches(file)
+            && self.rules.matches(file)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    fn file(name: &str, size: u64) -> FileInfo {
+        FileInfo::test(1, name, 1_700_000_000, size)
+    }
+
+    fn new_year_2023() -> chrono::NaiveDate {
+        chrono::NaiveDate::from_ymd_opt(2023, 1, 1).unwrap()
+    }
+
+    fn rules(min_size: u64, max_size: Option<u64>) -> FileRules {
+        FileRules {
+            min_size,
+            max_size,
+            ..FileRules::default()
+        }
+    }
+
+    #[test]
+    fn size_bounds_are_inclusive() {
+        let rules = rules(MB, Some(5 * MB));
+        assert!(!rules.matches(&file("a.jpg", MB - 1)));
+        assert!(rules.matches(&file("a.jpg", MB)));
+        assert!(rules.matches(&file("a.jpg", 5 * MB)));
+        assert!(!rules.matches(&file("a.jpg", 5 * MB + 1)));
+        assert!(FileRules::default().matches(&file("a.jpg", 0)));
+    }
+
+    #[test]
+    fn cleaned_files_use_the_recorded_size() {
+        let mut cleaned = file("a.jpg", 0);
+        cleaned.actual_size = None;
+        cleaned.file_size = (2 * MB) as i64;
+        assert!(rules(MB, None).matches(&cleaned));
+        assert!(!rules(0, Some(MB)).matches(&cleaned));
+
+        // 数据库中的异常负数按 0 处理
+        cleaned.file_size = -1;
+        assert!(!rules(1, None).matches(&cleaned));
+    }
+
+    #[test]
+    fn parses_extension_lists() {
+        assert_eq!(
+            parse_extensions("jpg, .PNG  gif，webp"),
+            Some(vec!["jpg".to_string(), "png".to_string(), "gif".to_string(), "webp".to_string()])
+        );
+        assert_eq!(parse_extensions(""), None);
+        assert_eq!(parse_extensions(" , . "), None);
+    }
+
+    #[test]
+    fn extensions_match_case_insensitively() {
+        let rules = FileRules {
+            extensions: parse_extensions("jpg png"),
+            ..FileRules::default()
+        };
+        assert!(rules.matches(&file("a.JPG", 1)));
+        assert!(rules.matches(&file("b.tar.png", 1)));
+        assert!(!rules.matches(&file("c.gif", 1)));
+        assert!(!rules.matches(&file("jpg", 1)));
+    }
+
+    #[test]
+    fn animation_filter_splits_gif_and_static() {
+        let animated = FileRules {
+            animation: AnimationFilter::Animated,
+            ..FileRules::default()
+        };
+        let still = FileRules {
+            animation: AnimationFilter::Static,
+            ..FileRules::default()
+        };
+        assert!(animated.matches(&file("a.GIF", 1)));
+        assert!(!animated.matches(&file("a.jpg", 1)));
+        assert!(!still.matches(&file("a.gif", 1)));
+        assert!(still.matches(&file("a.jpg", 1)));
+        assert!(still.matches(&file("noext", 1)));
+    }
+
+    #[test]
+    fn filter_combines_time_original_and_rules() {
+        let mut original = file("a.jpg", 10 * MB);
+        original.original = 1;
+        let compressed = file("b.jpg", 10 * MB);
+
+        let filter = FileFilter::new(TimeRange::All)
+            .with_original(OriginalFilter::CompressedOnly)
+            .with_rules(rules(MB, None));
+        assert!(!filter.matches(&original));
+        assert!(filter.matches(&compressed));
+        assert!(!filter.matches(&file("c.jpg", 1)));
+
+        // 测试文件的时间是 2023 年
+        let filter = FileFilter::new(TimeRange::Before(new_year_2023()));
+        assert!(!filter.matches(&compressed));
+    }
+
+    #[test]
+    fn selected_files_override_other_conditions() {
+        let small = file("a.jpg", 1);
+        let filter = FileFilter::new(TimeRange::Before(new_year_2023()))
+            .with_original(OriginalFilter::OriginalOnly)
+            .with_rules(rules(MB, None))
+            .with_files(HashSet::from([small.key()]));
+        assert!(filter.matches(&small));
+        assert!(!filter.matches(&file("b.jpg", 10 * MB)));
+    }
+}
@@ -0,0 +1,188 @@
+use std::collections::HashMap;
+use std::fmt::Display;
+use std::path::PathBuf;
+use std::sync::OnceLock;
+
+/// 内置的语言，其他语言可以放在配置目录的 locales/<名称>.toml 中
+const BUILTIN_LOCALES: [(&str, &str); 2] = [
+    ("zh-CN", include_str!("../locales/zh-CN.toml")),
+    ("en", include_str!("../locales/en.toml")),
+];
+
+/// 源语言，其他语言缺少的条目从这里取
+const FALLBACK_LOCALE: &str = "zh-CN";
+
+struct Catalog {
+    locale: String,
+    messages: HashMap<String, String>,
+    fallback: HashMap<String, String>,
+}
+
+static CATALOG: OnceLock<Catalog> = OnceLock::new();
+
+/// 选择界面语言：配置文件中的 locale 优先，其次是 LC_ALL / LC_MESSAGES / LANG
+///
+/// 必须在第一次调用 `t!` 之前调用，否则语言已经按环境变量选定，配置不会生效。
+pub fn init(configured: Option<&str>) -> &'static str {
+    let locale = configured
+        .map(normalize)
+        .unwrap_or_else(locale_from_env);
+    let result = CATALOG.set(load(locale));
+    debug_assert!(result.is_ok(), "i18n::init 在 t! 之后调用，配置中的 locale 被忽略");
+    &catalog().locale
+}
+
+fn catalog() -> &'static Catalog {
+    CATALOG.get_or_init(|| load(locale_from_env()))
+}
+
+fn locale_from_env() -> String {
+    let value = ["LC_ALL", "LC_MESSAGES", "LANG"]
+        .iter()
+        .filter_map(|name| std::env::var(name).ok())
+        .find(|v| !v.is_empty())
+        .unwrap_or_default();
+
+    // 未设置或 C/POSIX 时保持原来的中文界面
+    if value.is_empty() || value == "C" || value == "POSIX" || value.starts_with("C.") {
+        FALLBACK_LOCALE.to_string()
+    } else {
+        normalize(&value)
+    }
+}
+
+/// "zh_CN.UTF-8" -> "zh-CN"，"en_US" -> "en"
+fn normalize(value: &str) -> String {
+    let base = value.split(['.', '@']).next().unwrap_or(value).replace('_', "-");
+    let lower = base.to_ascii_lowercase();
+
+    if lower.starts_with("zh") {
+        FALLBACK_LOCALE.to_string()
+    } else if lower.starts_with("en") {
+        "en".to_string()
+    } else {
+        base
+    }
+}
+
+fn load(locale: String) -> Catalog {
+    let fallback = parse(builtin(FALLBACK_LOCALE).unwrap_or_default());
+
+    let mut messages = builtin(&locale).map(parse).unwrap_or_default();
+    // 用户提供的语言文件可以新增语言，也可以覆盖内置语言的部分条目
+    if let Some(content) = user_locale_file(&locale).and_then(|p| std::fs::read_to_string(p).ok()) {
+        messages.extend(parse(&content));
+    }
+
+    // 既不是内置语言也没有语言文件时使用英文
+    let locale = if messages.is_empty() {
+        messages = parse(builtin("en").unwrap_or_default());
+        "en".to_string()
+    } else {
+        locale
+    };
+
+    Catalog {
+        locale,
+        messages,
+        fallback,
+    }
+}
+
+fn builtin(locale: &str) -> Option<&'static str> {
+    BUILTIN_LOCALES
+        .iter()
+        .find(|(name, _)| *name == locale)
+        .map(|(_, content)| *content)
+}
+
+fn user_locale_file(locale: &str) -> Option<PathBuf> {
+    let path = dirs::config_dir()?
+        .join("qqcleaner")
+        .join("locales")
+        .join(format!("{}.toml", locale));
+    path.exists().then_some(path)
+}
+
+/// 把 `[section] key = "..."` 展开成 "section.key"
+fn parse(content: &str) -> HashMap<String, String> {
+    let mut messages = HashMap::new();
+    if let Ok(table) = content.parse::<toml::Table>() {
+        flatten("", &table, &mut messages);
+    }
+    messages
+}
+
+fn flatten(prefix: &str, table: &toml::Table, out: &mut HashMap<String, String>) {
+    for (key, value) in table {
+        let full_key = if prefix.is_empty() {
+            key.clone()
+        } else {
+            format!("{}.{}", prefix, key)
+        };
+
+        match value {
+            toml::Value::String(s) => {
+                out.insert(full_key, s.clone());
+            }
+            toml::Value::Table(t) => flatten(&full_key, t, out),
+            _ => {}
+        }
+    }
+}
+
+/// 查找当前语言的文本，找不到时依次退回源语言和 key 本身
+pub fn tr(key: &str) -> &str {
+    let catalog = catalog();
+    catalog
+        .messages
+        .get(key)
+        .or_else(|| catalog.fallback.get(key))
+        .map(String::as_str)
+        .unwrap_or(key)
+}
+
+/// 依次替换模板中的 `{}`，`{0}`、`{1}` 按位置替换，方便不同语言调整语序
+pub fn fill(template: &str, args: &[&dyn Display]) -> String {
+    let mut out = String::with_capacity(template.len());
+    let mut next = 0;
+    let mut rest = template;
+
+    while let Some(start) = rest.find('{') {
+        out.push_str(&rest[..start]);
+        let after = &rest[start + 1..];
+
+        match after.find('}') {
+            Some(end) if after[..end].chars().all(|c| c.is_ascii_digit()) => {
+                let index = if end == 0 {
+                    next += 1;
+                    next - 1
+                } else {
+                    after[..end].parse().unwrap_or(usize::MAX)
+                };
+                match args.get(index) {
+                    Some(arg) => out.push_str(&arg.to_string()),
+                    None => out.push_str(&rest[start..start + end + 2]),
+                }
+                rest = &after[end + 1..];
+            }
+            _ => {
+                out.push('{');
+                rest = after;
+            }
+        }
+    }
+
+    out.push_str(rest);
+    out
+}
+
+/// 取当前语言的文本：`t!("ui.title")` 返回 &str，带参数时返回填充后的 String
+macro_rules! t {
+    ($key:expr) => {
+        $crate::i18n::tr($key)
+    };
+    ($key:expr, $($arg:expr),+ $(,)?) => {
+        $crate::i18n::fill($crate::i18n::tr($key), &[$(&$arg as &dyn std::fmt::Display),+])
+    };
+}
@@ -0,0 +1,659 @@
+use anyhow::{bail, Result};
+use crossterm::event::{KeyCode, KeyEvent, KeyModifiers};
+use std::collections::HashMap;
+
+/// 按键生效的界面位置，同一个按键可以在不同位置绑定不同操作
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum KeyContext {
+    Global,
+    Analysis,
+    GroupList,
+    FileList,
+    Clean,
+    Migrate,
+    Logs,
+    Filter,
+    Confirm,
+}
+
+impl KeyContext {
+    /// 帮助对话框中的分组顺序
+    pub const ALL: [KeyContext; 9] = [
+        KeyContext::Global,
+        KeyContext::Analysis,
+        KeyContext::GroupList,
+        KeyContext::FileList,
+        KeyContext::Clean,
+        KeyContext::Migrate,
+        KeyContext::Logs,
+        KeyContext::Filter,
+        KeyContext::Confirm,
+    ];
+
+    /// 处理一次按键时会依次查找的位置组合，见 `event::handle_key_event`
+    ///
+    /// 对话框打开时只查找对话框本身；其他情况先查找全局按键，群列表和文件列表之后还会查找当前标签页的操作。
+    const LAYERS: [&'static [KeyContext]; 8] = [
+        &[KeyContext::Confirm],
+        &[KeyContext::Filter],
+        &[KeyContext::Global, KeyContext::Logs],
+        &[KeyContext::Global, KeyContext::Analysis],
+        &[KeyContext::Global, KeyContext::GroupList, KeyContext::Clean],
+        &[KeyContext::Global, KeyContext::GroupList, KeyContext::Migrate],
+        &[KeyContext::Global, KeyContext::FileList, KeyContext::Clean],
+        &[KeyContext::Global, KeyContext::FileList, KeyContext::Migrate],
+    ];
+
+    pub fn title(&self) -> &'static str {
+        match self {
+            KeyContext::Global => t!("key_context.global"),
+            KeyContext::Analysis => t!("key_context.analysis"),
+            KeyContext::GroupList => t!("key_context.group_list"),
+            KeyContext::FileList => t!("key_context.file_list"),
+            KeyContext::Clean => t!("key_context.clean"),
+            KeyContext::Migrate => t!("key_context.migrate"),
+            KeyContext::Logs => t!("key_context.logs"),
+            KeyContext::Filter => t!("key_context.filter"),
+            KeyContext::Confirm => t!("key_context.confirm"),
+        }
+    }
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Action {
+    Quit,
+    Help,
+    NextTab,
+    PrevTab,
+    TabAnalysis,
+    TabClean,
+    TabMigrate,
+    TabLogs,
+    TimeRange,
+    TimeRangeInput,
+    OriginalFilter,
+    GroupPolicy,
+    Confirm,
+    Cancel,
+    Apply,
+    Open,
+    Up,
+    Down,
+    PageUp,
+    PageDown,
+    Top,
+    Bottom,
+    Toggle,
+    SelectAll,
+    DeselectAll,
+    SelectQuit,
+    Pin,
+    Budget,
+    Back,
+    Search,
+    Sort,
+    ReverseSort,
+    Filter,
+    Preview,
+    Details,
+    Clean,
+    CleanScope,
+    Migrate,
+    Restore,
+    Doctor,
+    NextPath,
+    PrevPath,
+    LogFilter,
+    ChartOlder,
+    ChartNewer,
+    ChartMetric,
+    ChartSelected,
+    ChartView,
+    JumpToGroup,
+}
+
+impl Action {
+    /// 查找按键时的优先顺序：同一位置有多个操作绑定同一按键时，靠前的生效
+    pub const ALL: [Action; 49] = [
+        Action::Quit,
+        Action::Help,
+        Action::NextTab,
+        Action::PrevTab,
+        Action::TabAnalysis,
+        Action::TabClean,
+        Action::TabMigrate,
+        Action::TabLogs,
+        Action::TimeRange,
+        Action::TimeRangeInput,
+        Action::OriginalFilter,
+        Action::GroupPolicy,
+        Action::Confirm,
+        Action::Cancel,
+        Action::Apply,
+        Action::Open,
+        Action::Up,
+        Action::Down,
+        Action::PageUp,
+        Action::PageDown,
+        Action::Top,
+        Action::Bottom,
+        Action::Toggle,
+        Action::SelectAll,
+        Action::DeselectAll,
+        Action::SelectQuit,
+        Action::Pin,
+        Action::Budget,
+        Action::Back,
+        Action::Search,
+        Action::Sort,
+        Action::ReverseSort,
+        Action::Filter,
+        Action::Preview,
+        Action::Details,
+        Action::Clean,
+        Action::CleanScope,
+        Action::Migrate,
+        Action::Restore,
+        Action::Doctor,
+        Action::NextPath,
+        Action::PrevPath,
+        Action::LogFilter,
+        Action::ChartOlder,
+        Action::ChartNewer,
+        Action::ChartMetric,
+        Action::ChartSelected,
+        Action::ChartView,
+        Action::JumpToGroup,
+    ];
+
+    /// 配置文件 `[keymap]` 中使用的名称
+    pub fn name(&self) -> &'static str {
+        match self {
+            Action::Quit => "quit",
+            Action::Help => "help",
+            Action::NextTab => "next_tab",
+            Action::PrevTab => "prev_tab",
+            Action::TabAnalysis => "tab_analysis",
+            Action::TabClean => "tab_clean",
+            Action::TabMigrate => "tab_migrate",
+            Action::TabLogs => "tab_logs",
+            Action::TimeRange => "time_range",
+            Action::TimeRangeInput => "time_range_input",
+            Action::OriginalFilter => "original_filter",
+            Action::GroupPolicy => "group_policy",
+            Action::Confirm => "confirm",
+            Action::Cancel => "cancel",
+            Action::Apply => "apply",
+            Action::Open => "open",
+            Action::Up => "up",
+            Action::Down => "down",
+            Action::PageUp => "page_up",
+            Action::PageDown => "page_down",
+            Action::Top => "top",
+            Action::Bottom => "bottom",
+            Action::Toggle => "toggle",
+            Action::SelectAll => "select_all",
+            Action::DeselectAll => "deselect_all",
+            Action::SelectQuit => "select_quit",
+            Action::Pin => "pin",
+            Action::Budget => "budget",
+            Action::Back => "back",
+            Action::Search => "search",
+            Action::Sort => "sort",
+            Action::ReverseSort => "reverse_sort",
+            Action::Filter => "filter",
+            Action::Preview => "preview",
+            Action::Details => "details",
+            Action::Clean => "clean",
+            Action::CleanScope => "clean_scope",
+            Action::Migrate => "migrate",
+            Action::Restore => "restore",
+            Action::Doctor => "doctor",
+            Action::NextPath => "next_path",
+            Action::PrevPath => "prev_path",
+            Action::LogFilter => "log_filter",
+            Action::ChartOlder => "chart_older",
+            Action::ChartNewer => "chart_newer",
+            Action::ChartMetric => "chart_metric",
+            Action::ChartSelected => "chart_selected",
+            Action::ChartView => "chart_view",
+            Action::JumpToGroup => "jump_to_group",
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            Action::Quit => t!("action.quit"),
+            Action::Help => t!("action.help"),
+            Action::NextTab => t!("action.next_tab"),
+            Action::PrevTab => t!("action.prev_tab"),
+            Action::TabAnalysis => t!("action.tab_analysis"),
+            Action::TabClean => t!("action.tab_clean"),
+            Action::TabMigrate => t!("action.tab_migrate"),
+            Action::TabLogs => t!("action.tab_logs"),
+            Action::TimeRange => t!("action.time_range"),
+            Action::TimeRangeInput => t!("action.time_range_input"),
+            Action::OriginalFilter => t!("action.original_filter"),
+            Action::GroupPolicy => t!("action.group_policy"),
+            Action::Confirm => t!("action.confirm"),
+            Action::Cancel => t!("action.cancel"),
+            Action::Apply => t!("action.apply"),
+            Action::Open => t!("action.open"),
+            Action::Up => t!("action.up"),
+            Action::Down => t!("action.down"),
+            Action::PageUp => t!("action.page_up"),
+            Action::PageDown => t!("action.page_down"),
+            Action::Top => t!("action.top"),
+            Action::Bottom => t!("action.bottom"),
+            Action::Toggle => t!("action.toggle"),
+            Action::SelectAll => t!("action.select_all"),
+            Action::DeselectAll => t!("action.deselect_all"),
+            Action::SelectQuit => t!("action.select_quit"),
+            Action::Pin => t!("action.pin"),
+            Action::Budget => t!("action.budget"),
+            Action::Back => t!("action.back"),
+            Action::Search => t!("action.search"),
+            Action::Sort => t!("action.sort"),
+            Action::ReverseSort => t!("action.reverse_sort"),
+            Action::Filter => t!("action.filter"),
+            Action::Preview => t!("action.preview"),
+            Action::Details => t!("action.details"),
+            Action::Clean => t!("action.clean"),
+            Action::CleanScope => t!("action.clean_scope"),
+            Action::Migrate => t!("action.migrate"),
+            Action::Restore => t!("action.restore"),
+            Action::Doctor => t!("action.doctor"),
+            Action::NextPath => t!("action.next_path"),
+            Action::PrevPath => t!("action.prev_path"),
+            Action::LogFilter => t!("action.log_filter"),
+            Action::ChartOlder => t!("action.chart_older"),
+            Action::ChartNewer => t!("action.chart_newer"),
+            Action::ChartMetric => t!("action.chart_metric"),
+            Action::ChartSelected => t!("action.chart_selected"),
+            Action::ChartView => t!("action.chart_view"),
+            Action::JumpToGroup => t!("action.jump_to_group"),
+        }
+    }
+
+    /// 操作生效的位置，第一个用于帮助对话框分组
+    pub fn contexts(&self) -> &'static [KeyContext] {
+        use KeyContext::*;
+
+        match self {
+            Action::Quit
+            | Action::Help
+            | Action::NextTab
+            | Action::PrevTab
+            | Action::TabAnalysis
+            | Action::TabClean
+            | Action::TabMigrate
+            | Action::TabLogs
+            | Action::TimeRange
+            | Action::TimeRangeInput
+            | Action::OriginalFilter
+            | Action::GroupPolicy => &[Global],
+            Action::Confirm => &[Confirm],
+            Action::Cancel => &[Confirm, Filter],
+            Action::Apply => &[Filter],
+            Action::Open => &[GroupList, Analysis, Filter],
+            Action::Search
+            | Action::Filter
+            | Action::SelectQuit
+            | Action::Pin
+            | Action::Budget
+            | Action::Details => &[GroupList],
+            Action::Up | Action::Down => &[GroupList, FileList, Logs, Filter, Analysis],
+            Action::PageUp | Action::PageDown | Action::Top | Action::Bottom | Action::LogFilter => &[Logs],
+            Action::ChartOlder
+            | Action::ChartNewer
+            | Action::ChartMetric
+            | Action::ChartSelected
+            | Action::ChartView
+            | Action::JumpToGroup => &[Analysis],
+            Action::Toggle => &[GroupList, FileList, Filter, Confirm],
+            Action::SelectAll | Action::DeselectAll | Action::Sort => &[GroupList, FileList],
+            Action::Back => &[FileList, GroupList, Analysis],
+            Action::ReverseSort => &[GroupList, FileList],
+            Action::Preview => &[FileList],
+            Action::Clean | Action::CleanScope => &[Clean],
+            Action::Migrate | Action::Restore | Action::Doctor => &[Migrate],
+            Action::NextPath | Action::PrevPath => &[Migrate, Confirm],
+        }
+    }
+
+    fn default_keys(&self) -> &'static [&'static str] {
+        match self {
+            Action::Quit => &["q"],
+            Action::Help => &["?", "h"],
+            Action::NextTab => &["Tab"],
+            Action::PrevTab => &["BackTab"],
+            Action::TabAnalysis => &["1"],
+            Action::TabClean => &["2"],
+            Action::TabMigrate => &["3"],
+            Action::TabLogs => &["4"],
+            Action::TimeRange => &["t"],
+            Action::TimeRangeInput => &["T"],
+            Action::OriginalFilter => &["O"],
+            Action::GroupPolicy => &["P"],
+            Action::Confirm => &["y", "Y", "Enter"],
+            Action::Cancel => &["n", "N", "c", "Esc"],
+            Action::Apply => &["a"],
+            Action::Open => &["Enter"],
+            Action::Up => &["Up", "k"],
+            Action::Down => &["Down", "j"],
+            Action::PageUp => &["PageUp"],
+            Action::PageDown => &["PageDown"],
+            Action::Top => &["g", "Home"],
+            Action::Bottom => &["G", "End"],
+            Action::Toggle => &["Space"],
+            Action::SelectAll => &["a"],
+            Action::DeselectAll => &["A"],
+            Action::SelectQuit => &["Q"],
+            Action::Pin => &["K"],
+            Action::Budget => &["B"],
+            Action::Back => &["Esc", "Backspace"],
+            Action::Search => &["/"],
+            Action::Sort => &["s"],
+            Action::ReverseSort => &["S"],
+            Action::Filter => &["f"],
+            Action::Preview => &["v"],
+            Action::Details => &["i"],
+            Action::Clean => &["d", "Delete"],
+            Action::CleanScope => &["x"],
+            Action::Migrate => &["m"],
+            Action::Restore => &["r"],
+            Action::Doctor => &["o"],
+            Action::NextPath => &["Right"],
+            Action::PrevPath => &["Left"],
+            Action::LogFilter => &["l"],
+            Action::ChartOlder => &["Left", "["],
+            Action::ChartNewer => &["Right", "]"],
+            Action::ChartMetric => &["b"],
+            Action::ChartSelected => &["f"],
+            Action::ChartView => &["c"],
+            Action::JumpToGroup => &["g"],
+        }
+    }
+
+    fn from_name(name: &str) -> Option<Self> {
+        Action::ALL.into_iter().find(|a| a.name() == name)
+    }
+}
+
+/// 一个按键组合
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct KeyBinding {
+    code: KeyCode,
+    modifiers: KeyModifiers,
+}
+
+impl KeyBinding {
+    /// 解析 "q"、"Enter"、"Ctrl+d"、"Alt+Left" 这样的按键描述
+    pub fn parse(text: &str) -> Result<Self> {
+        let mut modifiers = KeyModifiers::NONE;
+        let mut rest = text;
+
+        // 单独的 "+" 是按键本身而不是分隔符
+        while let Some((prefix, tail)) = rest.split_once('+').filter(|(_, tail)| !tail.is_empty()) {
+            modifiers |= match prefix.to_ascii_lowercase().as_str() {
+                "ctrl" | "control" => KeyModifiers::CONTROL,
+                "alt" => KeyModifiers::ALT,
+                "shift" => KeyModifiers::SHIFT,
+                _ => bail!(t!("keymap.unknown_modifier", prefix, text)),
+            };
+            rest = tail;
+        }
+
+        let code = match rest.to_ascii_lowercase().as_str() {
+            "enter" => KeyCode::Enter,
+            "esc" | "escape" => KeyCode::Esc,
+            "tab" if modifiers.contains(KeyModifiers::SHIFT) => KeyCode::BackTab,
+            "tab" => KeyCode::Tab,
+            "backtab" => KeyCode::BackTab,
+            "space" => KeyCode::Char(' '),
+            "backspace" => KeyCode::Backspace,
+            "delete" | "del" => KeyCode::Delete,
+            "insert" => KeyCode::Insert,
+            "up" => KeyCode::Up,
+            "down" => KeyCode::Down,
+            "left" => KeyCode::Left,
+            "right" => KeyCode::Right,
+            "pageup" => KeyCode::PageUp,
+            "pagedown" => KeyCode::PageDown,
+            "home" => KeyCode::Home,
+            "end" => KeyCode::End,
+            lower => {
+                let mut chars = rest.chars();
+                match (chars.next(), chars.next()) {
+                    (Some(c), None) => KeyCode::Char(c),
+                    _ => match lower.strip_prefix('f').and_then(|n| n.parse::<u8>().ok()) {
+                        Some(n) if (1..=12).contains(&n) => KeyCode::F(n),
+                        _ => bail!(t!("keymap.unknown_key", text)),
+                    },
+                }
+            }
+        };
+
+        // 字符的大小写已经体现了 Shift，比较时不再区分
+        if matches!(code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+
+        Ok(Self { code, modifiers })
+    }
+
+    pub fn matches(&self, key: &KeyEvent) -> bool {
+        let mut modifiers = key.modifiers;
+        if matches!(key.code, KeyCode::Char(_) | KeyCode::BackTab) {
+            modifiers.remove(KeyModifiers::SHIFT);
+        }
+
+        key.code == self.code && modifiers == self.modifiers
+    }
+
+    /// 帮助和提示中显示的按键名称
+    pub fn label(&self) -> String {
+        let key = match self.code {
+            KeyCode::Char(' ') => "Space".to_string(),
+            KeyCode::Char(c) => c.to_string(),
+            KeyCode::Enter => "Enter".to_string(),
+            KeyCode::Esc => "Esc".to_string(),
+            KeyCode::Tab => "Tab".to_string(),
+            KeyCode::BackTab => "Shift+Tab".to_string(),
+            KeyCode::Backspace => "Backspace".to_string(),
+            KeyCode::Delete => "Delete".to_string(),
+            KeyCode::Insert => "Insert".to_string(),
+            KeyCode::Up => "↑".to_string(),
+            KeyCode::Down => "↓".to_string(),
+            KeyCode::Left => "←".to_string(),
+            KeyCode::Right => "→".to_string(),
+            KeyCode::PageUp => "PgUp".to_string(),
+            KeyCode::PageDown => "PgDn".to_string(),
+            KeyCode::Home => "Home".to_string(),
+            KeyCode::End => "End".to_string(),
+            KeyCode::F(n) => format!("F{}", n),
+            other => format!("{:?}", other),
+        };
+
+        let mut label = String::new();
+        if self.modifiers.contains(KeyModifiers::CONTROL) {
+            label.push_str("Ctrl+");
+        }
+        if self.modifiers.contains(KeyModifiers::ALT) {
+            label.push_str("Alt+");
+        }
+        if self.modifiers.contains(KeyModifiers::SHIFT) {
+            label.push_str("Shift+");
+        }
+        label + &key
+    }
+}
+
+/// 操作到按键的映射，默认值可以被配置文件的 `[keymap]` 覆盖
+#[derive(Debug, Clone)]
+pub struct Keymap {
+    bindings: HashMap<Action, Vec<KeyBinding>>,
+}
+
+impl Default for Keymap {
+    fn default() -> Self {
+        let bindings = Action::ALL
+            .into_iter()
+            .map(|action| {
+                let keys = action
+                    .default_keys()
+                    .iter()
+                    .map(|k| KeyBinding::parse(k).expect("默认按键必须有效"))
+                    .collect();
+                (action, keys)
+            })
+            .collect();
+
+        Self { bindings }
+    }
+}
+
+impl Keymap {
+    /// 在默认按键的基础上应用配置，配置中出现的操作完全替换默认按键
+    pub fn from_config(overrides: &HashMap<String, Vec<String>>) -> Result<Self> {
+        let mut keymap = Self::default();
+
+        for (name, keys) in overrides {
+            let Some(action) = Action::from_name(name) else {
+                bail!(t!("keymap.unknown_action", name));
+            };
+
+            let bindings = keys
+                .iter()
+                .map(|k| KeyBinding::parse(k))
+                .collect::<Result<Vec<_>>>()?;
+            keymap.bindings.insert(action, bindings);
+        }
+
+        keymap.check_conflicts()?;
+        Ok(keymap)
+    }
+
+    /// 同一次按键会依次查找的位置中，不允许两个操作使用相同的按键
+    fn check_conflicts(&self) -> Result<()> {
+        for layer in KeyContext::LAYERS {
+            let mut seen: Vec<(KeyBinding, Action)> = Vec::new();
+            let actions = Action::ALL
+                .into_iter()
+                .filter(|action| action.contexts().iter().any(|c| layer.contains(c)));
+
+            for action in actions {
+                for binding in self.keys(action) {
+                    if let Some((_, other)) = seen.iter().find(|(b, a)| b == binding && *a != action) {
+                        bail!(t!("keymap.conflict", binding.label(), other.name(), action.name()));
+                    }
+                    seen.push((*binding, action));
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// 查找按键在指定位置对应的操作
+    pub fn action(&self, key: &KeyEvent, context: KeyContext) -> Option<Action> {
+        Action::ALL.into_iter().find(|action| {
+            action.contexts().contains(&context) && self.keys(*action).iter().any(|b| b.matches(key))
+        })
+    }
+
+    pub fn keys(&self, action: Action) -> &[KeyBinding] {
+        self.bindings.get(&action).map(Vec::as_slice).unwrap_or(&[])
+    }
+
+    /// 操作的所有按键，用 "/" 连接，例如 "d/Delete"
+    pub fn label(&self, action: Action) -> String {
+        let labels: Vec<String> = self.keys(action).iter().map(KeyBinding::label).collect();
+        if labels.is_empty() {
+            t!("keymap.unbound").to_string()
+        } else {
+            labels.join("/")
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn key(code: KeyCode, modifiers: KeyModifiers) -> KeyEvent {
+        KeyEvent::new(code, modifiers)
+    }
+
+    fn overrides(entries: &[(&str, &[&str])]) -> HashMap<String, Vec<String>> {
+        entries
+            .iter()
+            .map(|(name, keys)| (name.to_string(), keys.iter().map(|k| k.to_string()).collect()))
+            .collect()
+    }
+
+    #[test]
+    fn parses_named_keys_and_modifiers() {
+        let binding = KeyBinding::parse("Ctrl+d").unwrap();
+        assert!(binding.matches(&key(KeyCode::Char('d'), KeyModifiers::CONTROL)));
+        assert!(!binding.matches(&key(KeyCode::Char('d'), KeyModifiers::NONE)));
+
+        assert!(KeyBinding::parse("pagedown").unwrap().matches(&key(KeyCode::PageDown, KeyModifiers::NONE)));
+        assert!(KeyBinding::parse("F5").unwrap().matches(&key(KeyCode::F(5), KeyModifiers::NONE)));
+        assert!(KeyBinding::parse("Shift+Tab").unwrap().matches(&key(KeyCode::BackTab, KeyModifiers::SHIFT)));
+        // 单独的 "+" 是按键本身
+        assert!(KeyBinding::parse("+").unwrap().matches(&key(KeyCode::Char('+'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn uppercase_chars_ignore_shift() {
+        let binding = KeyBinding::parse("A").unwrap();
+        assert!(binding.matches(&key(KeyCode::Char('A'), KeyModifiers::SHIFT)));
+        assert!(binding.matches(&key(KeyCode::Char('A'), KeyModifiers::NONE)));
+        assert!(!binding.matches(&key(KeyCode::Char('a'), KeyModifiers::NONE)));
+    }
+
+    #[test]
+    fn rejects_unknown_keys_and_modifiers() {
+        assert!(KeyBinding::parse("Hyper+x").is_err());
+        assert!(KeyBinding::parse("F13").is_err());
+        assert!(KeyBinding::parse("nope").is_err());
+    }
+
+    #[test]
+    fn default_keymap_has_no_conflicts() {
+        Keymap::default().check_conflicts().unwrap();
+    }
+
+    #[test]
+    fn overrides_replace_default_keys() {
+        let keymap = Keymap::from_config(&overrides(&[("quit", &["Ctrl+q"])])).unwrap();
+        let ctrl_q = key(KeyCode::Char('q'), KeyModifiers::CONTROL);
+        assert_eq!(keymap.action(&ctrl_q, KeyContext::Global), Some(Action::Quit));
+        assert_eq!(keymap.action(&key(KeyCode::Char('q'), KeyModifiers::NONE), KeyContext::Global), None);
+    }
+
+    #[test]
+    fn rejects_unknown_actions() {
+        assert!(Keymap::from_config(&overrides(&[("launch_rockets", &["x"])])).is_err());
+    }
+
+    #[test]
+    fn rejects_conflicts_in_the_same_context() {
+        // 群列表中 s 已经用于排序
+        assert!(Keymap::from_config(&overrides(&[("details", &["s"])])).is_err());
+        // 全局按键会先于群列表处理
+        assert!(Keymap::from_config(&overrides(&[("search", &["t"])])).is_err());
+        // 清理页的操作在群列表之后查找
+        assert!(Keymap::from_config(&overrides(&[("clean", &["f"])])).is_err());
+    }
+
+    #[test]
+    fn allows_same_key_in_separate_contexts() {
+        // 日志页和迁移页不会处理同一次按键
+        let keymap = Keymap::from_config(&overrides(&[("log_filter", &["m"])])).unwrap();
+        let m = key(KeyCode::Char('m'), KeyModifiers::NONE);
+        assert_eq!(keymap.action(&m, KeyContext::Logs), Some(Action::LogFilter));
+        assert_eq!(keymap.action(&m, KeyContext::Migrate), Some(Action::Migrate));
+    }
+}
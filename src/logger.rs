@@ -12,7 +12,7 @@ impl Logger {
     pub fn new() -> Result<Self> {
         let log_dir = Self::get_log_directory()?;
         fs::create_dir_all(&log_dir)
-            .with_context(|| format!("创建日志目录失败: {:?}", log_dir))?;
+            .with_context(|| t!("error.create_log_dir", log_dir.display()))?;
 
         let log_filename = format!("qqcleaner_{}.log", Local::now().format("%Y%m%d_%H%M%S"));
         let log_file = log_dir.join(log_filename);
@@ -23,8 +23,8 @@ impl Logger {
             .open(&log_file)?;
 
         writeln!(file, "{}", "=".repeat(80))?;
-        writeln!(file, "QQCleaner - 日志记录")?;
-        writeln!(file, "启动时间: {}", Local::now().format("%Y-%m-%d %H:%M:%S"))?;
+        writeln!(file, "QQCleaner - {}", t!("log.header"))?;
+        writeln!(file, "{}", t!("log.started_at", Local::now().format("%Y-%m-%d %H:%M:%S")))?;
         writeln!(file, "{}", "=".repeat(80))?;
         writeln!(file)?;
 
@@ -42,14 +42,14 @@ impl Logger {
         #[cfg(target_os = "macos")]
         {
             let home = dirs::home_dir()
-                .context("无法获取用户主目录")?;
+                .context(t!("error.no_home"))?;
             Ok(home.join("Library").join("Logs").join("qqcleaner"))
         }
 
         #[cfg(not(target_os = "macos"))]
         {
             let cache_dir = dirs::cache_dir()
-                .context("无法获取缓存目录")?;
+                .context(t!("error.no_cache_dir"))?;
             Ok(cache_dir.join("qqcleaner").join("logs"))
         }
     }
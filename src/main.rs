@@ -1,13 +1,26 @@
+#[macro_use]
+mod i18n;
+
 mod app;
+mod cli;
 mod config;
 mod database;
 mod decryptor;
 mod event;
 mod file_checker;
+mod file_filter;
+mod keymap;
 mod logger;
 mod migrator;
 mod models;
+mod path_sanitizer;
+mod policy;
+mod preflight;
+mod preview;
+mod projection;
+mod search;
 mod time_range;
+mod treemap;
 mod ui;
 
 use anyhow::{Context, Result};
@@ -16,32 +29,51 @@ use crossterm::{
     execute,
     terminal::{disable_raw_mode, enable_raw_mode, EnterAlternateScreen, LeaveAlternateScreen},
 };
-use ratatui::{backend::CrosstermBackend, Terminal};
+use ratatui::{backend::CrosstermBackend, layout::Rect, Terminal};
 use std::path::PathBuf;
 
-use app::{App, ConfirmAction, LogLevel};
+use app::{App, ConfirmAction, LogLevel, MigrateMode};
 use config::Config;
 use database::Database;
 use decryptor::Decryptor;
 use event::{AppEvent, EventHandler};
 use file_checker::FileChecker;
+use keymap::Keymap;
 use logger::Logger;
 use migrator::{MigrateOptions, Migrator};
 use std::sync::Arc;
 
 #[tokio::main]
 async fn main() -> Result<()> {
+    let config = Config::load()?;
+    i18n::init(config.locale.as_deref());
+
+    let command = cli::parse(std::env::args().skip(1))?;
+    if let cli::Command::Help = command {
+        println!("{}", t!("cli.usage"));
+        return Ok(());
+    }
+
     let logger = Arc::new(Logger::new()?);
-    println!("日志文件: {:?}", logger.get_log_path());
+    println!("{}", t!("cli.log_file", logger.get_log_path().display()));
+
+    let keymap = Keymap::from_config(&config.keymap)?;
+    let (stats, nt_data_dir) = initialize_app(&config).await?;
+
+    if let cli::Command::Clean(mut args) = command {
+        args.pinned.extend(config.pinned_groups.iter().cloned());
+        let checker = FileChecker::new(nt_data_dir);
+        return cli::run_clean(&args, &stats, &checker, &logger).await;
+    }
 
-    let (stats, nt_data_dir) = initialize_app().await?;
     enable_raw_mode()?;
     let mut stdout = std::io::stdout();
     execute!(stdout, EnterAlternateScreen, EnableMouseCapture)?;
     let backend = CrosstermBackend::new(stdout);
     let mut terminal = Terminal::new(backend)?;
 
-    let mut app = App::new(stats, logger);
+    let mut app = App::new(stats, logger, nt_data_dir.clone(), keymap);
+    app.pinned_groups = config.pinned_groups.iter().cloned().collect();
     let event_handler = EventHandler::new();
     let checker = FileChecker::new(nt_data_dir.clone());
     let migrator = Migrator::new(nt_data_dir.clone());
@@ -56,21 +88,19 @@ async fn main() -> Result<()> {
     terminal.show_cursor()?;
 
     if let Err(err) = result {
-        eprintln!("应用错误: {}", err);
+        eprintln!("{}", t!("cli.app_error", err));
     }
 
     Ok(())
 }
 
-async fn initialize_app() -> Result<(Vec<crate::models::GroupStats>, PathBuf)> {
-    println!("\n正在初始化...");
-
-    let config = Config::load()?;
-    println!("✓ 配置加载成功");
+async fn initialize_app(config: &Config) -> Result<(Vec<crate::models::GroupStats>, PathBuf)> {
+    println!("\n{}", t!("cli.initializing"));
+    println!("{}", t!("cli.config_loaded"));
 
     let qq_base_dir = config.get_qq_base_dir()?;
     if !qq_base_dir.exists() {
-        anyhow::bail!("未找到 QQ 数据目录");
+        anyhow::bail!(t!("cli.qq_dir_missing"));
     }
 
     let mut nt_qq_dir: Option<PathBuf> = None;
@@ -91,40 +121,40 @@ async fn initialize_app() -> Result<(Vec<crate::models::GroupStats>, PathBuf)> {
         }
     }
 
-    let nt_qq_dir = nt_qq_dir.context("未找到 nt_qq_* 目录")?;
+    let nt_qq_dir = nt_qq_dir.context(t!("cli.nt_qq_dir_missing"))?;
     let nt_data_dir = nt_qq_dir.join(&config.paths.nt_data_subpath);
 
     if !nt_data_dir.exists() {
-        anyhow::bail!("未找到 nt_data 目录: {:?}", nt_data_dir);
+        anyhow::bail!(t!("cli.nt_data_dir_missing", nt_data_dir.display()));
     }
 
-    println!("✓ 找到数据目录");
+    println!("{}", t!("cli.data_dir_found"));
 
-    println!("正在检查数据库状态...");
+    println!("{}", t!("cli.checking_db"));
 
     let local_db_dir = config.get_db_dir();
 
     // 显示当前使用的工作目录
     #[cfg(debug_assertions)]
-    println!("数据库工作目录 (debug): {:?}", local_db_dir);
+    println!("{}", t!("cli.db_dir_debug", local_db_dir.display()));
 
     #[cfg(not(debug_assertions))]
-    println!("数据库工作目录 (release): {:?}", local_db_dir);
-
-    println!("\n=== 重要提示 ===");
-    println!("本程序不会自动访问或复制任何应用的数据。");
-    println!("如需使用本程序，您需要：");
-    println!("1. 手动复制数据库文件到工作目录");
-    println!("2. 确认您拥有对这些数据的合法访问权");
-    println!("3. 理解您正在访问的数据内容");
-    println!("4. 自行承担数据访问的法律责任");
+    println!("{}", t!("cli.db_dir_release", local_db_dir.display()));
+
+    println!("\n{}", t!("cli.notice_title"));
+    println!("{}", t!("cli.notice_1"));
+    println!("{}", t!("cli.notice_2"));
+    println!("{}", t!("cli.notice_3"));
+    println!("{}", t!("cli.notice_4"));
+    println!("{}", t!("cli.notice_5"));
+    println!("{}", t!("cli.notice_6"));
     println!("================\n");
     let local_files_db = config.get_files_db_path_in(&local_db_dir);
     let local_group_db = config.get_group_db_path_in(&local_db_dir);
 
     // 检查是否已有解密后的数据库
     let (files_db, group_db) = if local_files_db.exists() && local_group_db.exists() {
-        println!("✓ 找到已解密的数据库: {:?}", local_db_dir);
+        println!("{}", t!("cli.decrypted_db_found", local_db_dir.display()));
         (local_files_db, local_group_db)
     } else {
         // 检查用户是否手动复制了原始数据库
@@ -132,16 +162,16 @@ async fn initialize_app() -> Result<(Vec<crate::models::GroupStats>, PathBuf)> {
         let source_group_db = local_db_dir.join("group_info.db");
 
         if !source_files_db.exists() || !source_group_db.exists() {
-            println!("⚠ 未找到数据库文件");
-            println!("\n请按以下步骤操作：");
-            println!("1. 手动复制以下文件到工作目录：");
+            println!("{}", t!("cli.db_missing"));
+            println!("\n{}", t!("cli.steps"));
+            println!("{}", t!("cli.step_copy"));
             println!("   - files_in_chat.db");
             println!("   - group_info.db");
 
             // 获取 QQ 数据库源目录
             let nt_db_source_dir = nt_qq_dir.join("nt_db");
 
-            println!("\n2. 源目录（从这里复制）：");
+            println!("\n{}", t!("cli.step_source"));
             println!(
                 "   {:?}",
                 nt_db_source_dir
@@ -149,7 +179,7 @@ async fn initialize_app() -> Result<(Vec<crate::models::GroupStats>, PathBuf)> {
                     .unwrap_or(nt_db_source_dir.clone())
             );
 
-            println!("\n3. 目标目录（复制到这里）：");
+            println!("\n{}", t!("cli.step_target"));
             println!(
                 "   {:?}",
                 local_db_dir.canonicalize().unwrap_or(local_db_dir.clone())
@@ -157,12 +187,12 @@ async fn initialize_app() -> Result<(Vec<crate::models::GroupStats>, PathBuf)> {
 
             if !local_db_dir.exists() {
                 std::fs::create_dir_all(&local_db_dir)
-                    .with_context(|| format!("创建数据库目录失败: {:?}", local_db_dir))?;
-                println!("\n✓ 已创建目标目录");
+                    .with_context(|| t!("cli.create_db_dir_failed", local_db_dir.display()))?;
+                println!("\n{}", t!("cli.target_dir_created"));
             }
 
             // 打开两个文件管理器窗口
-            println!("\n正在为您打开源目录和目标目录...");
+            println!("\n{}", t!("cli.opening_dirs"));
 
             #[cfg(target_os = "macos")]
             {
@@ -197,24 +227,24 @@ async fn initialize_app() -> Result<(Vec<crate::models::GroupStats>, PathBuf)> {
                     .spawn();
             }
 
-            println!("\n复制完成后，请重新运行本程序。");
-            anyhow::bail!("等待用户手动复制数据库文件");
+            println!("\n{}", t!("cli.rerun_after_copy"));
+            anyhow::bail!(t!("cli.waiting_for_copy"));
         }
 
         // 用户已复制文件，现在进行解密
-        println!("✓ 找到数据库文件");
+        println!("{}", t!("cli.db_files_found"));
 
         match Decryptor::new() {
             Ok(decryptor) => {
-                println!("✓ 找到密钥文件: {:?}", decryptor.get_key_path());
-                println!("开始解密数据库...");
+                println!("{}", t!("cli.key_found", decryptor.get_key_path().display()));
+                println!("{}", t!("cli.decrypting"));
 
                 let db_files = ["files_in_chat.db", "group_info.db"];
                 decryptor
                     .decrypt_databases(&local_db_dir, &local_db_dir, &db_files)
-                    .context("数据库解密失败")?;
+                    .context(t!("cli.decrypt_failed"))?;
 
-                println!("✓ 数据库解密完成");
+                println!("{}", t!("cli.decrypted"));
 
                 (
                     config.get_files_db_path_in(&local_db_dir),
@@ -222,41 +252,44 @@ async fn initialize_app() -> Result<(Vec<crate::models::GroupStats>, PathBuf)> {
                 )
             }
             Err(e) => {
-                println!("⚠ 未找到密钥文件");
-                println!("  提示：请将 sqlcipher.key 放在项目根目录或 ~/.config/qqcleaner/ 目录");
-                println!("  错误详情: {}", e);
-                anyhow::bail!("无法解密数据库：缺少密钥文件");
+                println!("{}", t!("cli.key_missing"));
+                println!("{}", t!("cli.key_hint"));
+                println!("{}", t!("cli.error_details", e));
+                anyhow::bail!(t!("cli.cannot_decrypt_no_key"));
             }
         }
     };
 
     if !files_db.exists() {
-        anyhow::bail!("未找到文件数据库: {:?}", files_db);
+        anyhow::bail!(t!("cli.files_db_missing", files_db.display()));
     }
 
     if !group_db.exists() {
-        anyhow::bail!("未找到群组数据库: {:?}", group_db);
+        anyhow::bail!(t!("cli.group_db_missing", group_db.display()));
     }
 
-    let db = Database::new(&files_db, &group_db).context("打开数据库失败")?;
-    println!("✓ 数据库打开成功");
+    let db = Database::new(&files_db, &group_db).context(t!("cli.open_db_failed"))?;
+    println!("{}", t!("cli.db_opened"));
 
-    let group_files = db.group_files_by_peer().context("读取文件信息失败")?;
-    let groups = db.get_all_groups().context("读取群组信息失败")?;
+    let group_files = db.group_files_by_peer().context(t!("cli.read_files_failed"))?;
+    let groups = db.get_all_groups().context(t!("cli.read_groups_failed"))?;
 
     println!(
-        "✓ 找到 {} 个群组，共 {} 个文件",
-        group_files.len(),
-        group_files.values().map(|v| v.len()).sum::<usize>()
+        "{}",
+        t!(
+            "cli.groups_found",
+            group_files.len(),
+            group_files.values().map(|v| v.len()).sum::<usize>()
+        )
     );
 
-    println!("正在分析文件（这可能需要一些时间）...");
+    println!("{}", t!("cli.analyzing"));
     let checker = FileChecker::new(nt_data_dir.clone());
     let group_files_vec: Vec<_> = group_files.into_iter().collect();
     let stats = checker
         .generate_group_stats(group_files_vec, &groups)
         .await?;
-    println!("✓ 分析完成\n");
+    println!("{}\n", t!("cli.analyzed"));
 
     Ok((stats, nt_data_dir))
 }
@@ -270,9 +303,18 @@ async fn run_app(
 ) -> Result<()> {
     let mut pending_clean = false;
     let mut pending_migrate = false;
+    let mut pending_restore = false;
 
     loop {
+        let size = terminal.size()?;
+        app.preview.area = ui::preview_area(Rect::new(0, 0, size.width, size.height), app);
+        let target = app.preview_target();
+        if app.preview.update(target) {
+            terminal.clear()?;
+        }
+
         terminal.draw(|f| ui::draw(f, app))?;
+        app.preview.emit(terminal.backend_mut())?;
 
         if pending_clean {
             pending_clean = false;
@@ -284,20 +326,34 @@ async fn run_app(
             execute_migrate(app, migrator, checker).await?;
         }
 
+        if pending_restore {
+            pending_restore = false;
+            execute_restore(app, migrator, checker).await?;
+        }
+
+        if app.doctor_requested {
+            app.doctor_requested = false;
+            execute_doctor(app, checker).await;
+        }
+
         match event_handler.next()? {
             AppEvent::Key(key) => {
                 event::handle_key_event(app, key);
+            }
+            AppEvent::Mouse(mouse) => {
+                event::handle_mouse_event(app, mouse, Rect::new(0, 0, size.width, size.height));
+            }
+            AppEvent::Tick => {}
+        }
 
-                if !app.show_confirm_dialog {
-                    if let Some(action) = app.confirm_action.take() {
-                        match action {
-                            ConfirmAction::Clean => pending_clean = true,
-                            ConfirmAction::Migrate => pending_migrate = true,
-                        }
-                    }
+        if !app.show_confirm_dialog {
+            if let Some(action) = app.confirm_action.take() {
+                match action {
+                    ConfirmAction::Clean => pending_clean = true,
+                    ConfirmAction::Migrate => pending_migrate = true,
+                    ConfirmAction::Restore => pending_restore = true,
                 }
             }
-            AppEvent::Tick => {}
         }
 
         if app.should_quit {
@@ -330,21 +386,27 @@ async fn execute_clean(app: &mut App, checker: &FileChecker) -> Result<()> {
 
     app.add_log(
         LogLevel::Info,
-        &format!("开始清理 {} 个群组", selected_info.len()),
+        &t!(
+            "log.clean_start",
+            selected_info.len(),
+            app.clean_scope.description()
+        ),
     );
 
     let total_files: usize = selected_info.iter().map(|(_, _, count)| count).sum();
     app.start_operation(total_files);
 
-    let time_range = app.time_range;
     let mut current = 0;
     let mut updated_indices = Vec::new();
 
     for (idx, group_name, file_count) in selected_info {
-        app.add_log(LogLevel::Info, &format!("清理群组: {}", group_name));
+        app.add_log(LogLevel::Info, &t!("log.clean_group", group_name));
 
         let stat = &app.stats[idx];
-        match checker.delete_group_files(stat, Some(&time_range)).await {
+        match checker
+            .delete_group_files(stat, Some(&app.clean_filter_for(stat)), app.clean_scope)
+            .await
+        {
             Ok((deleted, failed)) => {
                 current += file_count;
                 app.update_progress(current, &group_name);
@@ -352,12 +414,12 @@ async fn execute_clean(app: &mut App, checker: &FileChecker) -> Result<()> {
                 if failed > 0 {
                     app.add_log(
                         LogLevel::Warning,
-                        &format!("{}: 成功 {} 个, 失败 {} 个", group_name, deleted, failed),
+                        &t!("log.partial_result", group_name, deleted, failed),
                     );
                 } else {
                     app.add_log(
                         LogLevel::Success,
-                        &format!("{}: 成功删除 {} 个文件", group_name, deleted),
+                        &t!("log.clean_done_group", group_name, deleted),
                     );
                 }
 
@@ -368,21 +430,21 @@ async fn execute_clean(app: &mut App, checker: &FileChecker) -> Result<()> {
             Err(e) => {
                 app.add_log(
                     LogLevel::Error,
-                    &format!("{}: 删除失败 - {}", group_name, e),
+                    &t!("log.clean_failed", group_name, e),
                 );
             }
         }
     }
 
     if !updated_indices.is_empty() {
-        app.add_log(LogLevel::Info, "正在更新统计信息...");
+        app.add_log(LogLevel::Info, t!("log.updating_stats"));
         for idx in updated_indices {
             if let Some(stat) = app.stats.get_mut(idx) {
                 let group_name = stat.group_name.clone();
                 if let Err(e) = checker.update_group_stats(stat).await {
                     app.add_log(
                         LogLevel::Warning,
-                        &format!("更新群组 {} 统计信息失败: {}", group_name, e),
+                        &t!("log.update_stats_failed", group_name, e),
                     );
                 }
             }
@@ -391,8 +453,8 @@ async fn execute_clean(app: &mut App, checker: &FileChecker) -> Result<()> {
     }
 
     app.finish_operation();
-    app.add_log(LogLevel::Success, "清理操作完成");
-    app.selected_groups = vec![false; app.stats.len()];
+    app.add_log(LogLevel::Success, t!("log.clean_finished"));
+    app.clear_selection();
 
     Ok(())
 }
@@ -419,7 +481,11 @@ async fn execute_migrate(app: &mut App, migrator: &Migrator, checker: &FileCheck
 
     app.add_log(
         LogLevel::Info,
-        &format!("开始迁移 {} 个群组", selected_info.len()),
+        &t!(
+            "log.migrate_start",
+            selected_info.len(),
+            app.time_range.description()
+        ),
     );
 
     let total_files: usize = selected_info.iter().map(|(_, _, count)| count).sum();
@@ -428,27 +494,33 @@ async fn execute_migrate(app: &mut App, migrator: &Migrator, checker: &FileCheck
     let options = MigrateOptions {
         target_dir: app.migrate_target_path.clone(),
         keep_structure: true,
-        delete_after_migrate: !app.get_migrate_keep_original(),
+        delete_after_migrate: app.get_migrate_mode() == MigrateMode::Move,
+        link_back: app.get_migrate_mode() == MigrateMode::Link,
     };
 
     let mut current = 0;
     let mut updated_indices = Vec::new();
-    let should_update = !app.get_migrate_keep_original();
+    let should_update = app.get_migrate_mode().modifies_source();
 
     for (idx, group_name, file_count) in selected_info {
-        app.add_log(LogLevel::Info, &format!("迁移群组: {}", group_name));
+        app.add_log(LogLevel::Info, &t!("log.migrate_group", group_name));
 
         let stat = &app.stats[idx];
-        match migrator.migrate_group_files(stat, &options, None).await {
+        match migrator
+            .migrate_group_files(stat, &options, Some(&app.file_filter_for(stat)), None)
+            .await {
             Ok(result) => {
                 current += file_count;
                 app.update_progress(current, &group_name);
 
+                for error in &result.errors {
+                    app.add_log(LogLevel::Error, error);
+                }
                 if result.failed_files > 0 {
                     app.add_log(
                         LogLevel::Warning,
-                        &format!(
-                            "{}: 成功 {} 个, 失败 {} 个, 大小: {}",
+                        &t!(
+                            "log.migrate_partial",
                             group_name,
                             result.migrated_files,
                             result.failed_files,
@@ -458,8 +530,8 @@ async fn execute_migrate(app: &mut App, migrator: &Migrator, checker: &FileCheck
                 } else {
                     app.add_log(
                         LogLevel::Success,
-                        &format!(
-                            "{}: 成功迁移 {} 个文件, 大小: {}",
+                        &t!(
+                            "log.migrate_done_group",
                             group_name,
                             result.migrated_files,
                             crate::models::format_bytes(result.total_size)
@@ -474,21 +546,131 @@ async fn execute_migrate(app: &mut App, migrator: &Migrator, checker: &FileCheck
             Err(e) => {
                 app.add_log(
                     LogLevel::Error,
-                    &format!("{}: 迁移失败 - {}", group_name, e),
+                    &t!("log.migrate_failed", group_name, e),
+                );
+            }
+        }
+    }
+
+    if !updated_indices.is_empty() {
+        app.add_log(LogLevel::Info, t!("log.updating_stats"));
+        for idx in updated_indices {
+            if let Some(stat) = app.stats.get_mut(idx) {
+                let group_name = stat.group_name.clone();
+                if let Err(e) = checker.update_group_stats(stat).await {
+                    app.add_log(
+                        LogLevel::Warning,
+                        &t!("log.update_stats_failed", group_name, e),
+                    );
+                }
+            }
+        }
+        app.apply_sort();
+    }
+
+    app.finish_operation();
+    app.add_log(LogLevel::Success, t!("log.migrate_finished"));
+    app.clear_selection();
+
+    Ok(())
+}
+
+async fn execute_restore(app: &mut App, migrator: &Migrator, checker: &FileChecker) -> Result<()> {
+    let selected_info: Vec<(usize, String, usize)> = app
+        .selected_groups
+        .iter()
+        .enumerate()
+        .filter_map(|(idx, &selected)| {
+            if selected {
+                app.stats
+                    .get(idx)
+                    .map(|s| (idx, s.group_name.clone(), s.file_count))
+            } else {
+                None
+            }
+        })
+        .collect();
+
+    if selected_info.is_empty() {
+        return Ok(());
+    }
+
+    app.add_log(
+        LogLevel::Info,
+        &t!(
+            "log.restore_start",
+            app.migrate_target_path.display(),
+            selected_info.len()
+        ),
+    );
+
+    let total_files: usize = selected_info.iter().map(|(_, _, count)| count).sum();
+    app.start_operation(total_files);
+
+    let mut current = 0;
+    let mut updated_indices = Vec::new();
+
+    for (idx, group_name, file_count) in selected_info {
+        app.add_log(LogLevel::Info, &t!("log.restore_group", group_name));
+
+        let stat = &app.stats[idx];
+        match migrator
+            .restore_group_files(stat, &app.migrate_target_path, Some(&app.file_filter_for(stat)))
+            .await
+        {
+            Ok(result) => {
+                current += file_count;
+                app.update_progress(current, &group_name);
+
+                for error in &result.errors {
+                    app.add_log(LogLevel::Error, error);
+                }
+                if result.failed_files > 0 {
+                    app.add_log(
+                        LogLevel::Warning,
+                        &t!(
+                            "log.restore_partial",
+                            group_name,
+                            result.restored_files,
+                            result.skipped_files,
+                            result.failed_files
+                        ),
+                    );
+                } else {
+                    app.add_log(
+                        LogLevel::Success,
+                        &t!(
+                            "log.restore_done_group",
+                            group_name,
+                            result.restored_files,
+                            crate::models::format_bytes(result.total_size),
+                            result.skipped_files
+                        ),
+                    );
+                }
+
+                if result.restored_files > 0 {
+                    updated_indices.push(idx);
+                }
+            }
+            Err(e) => {
+                app.add_log(
+                    LogLevel::Error,
+                    &t!("log.restore_failed", group_name, e),
                 );
             }
         }
     }
 
     if !updated_indices.is_empty() {
-        app.add_log(LogLevel::Info, "正在更新统计信息...");
+        app.add_log(LogLevel::Info, t!("log.updating_stats"));
         for idx in updated_indices {
             if let Some(stat) = app.stats.get_mut(idx) {
                 let group_name = stat.group_name.clone();
                 if let Err(e) = checker.update_group_stats(stat).await {
                     app.add_log(
                         LogLevel::Warning,
-                        &format!("更新群组 {} 统计信息失败: {}", group_name, e),
+                        &t!("log.update_stats_failed", group_name, e),
                     );
                 }
             }
@@ -497,8 +679,36 @@ async fn execute_migrate(app: &mut App, migrator: &Migrator, checker: &FileCheck
     }
 
     app.finish_operation();
-    app.add_log(LogLevel::Success, "迁移操作完成");
-    app.selected_groups = vec![false; app.stats.len()];
+    app.add_log(LogLevel::Success, t!("log.restore_finished"));
+    app.clear_selection();
 
     Ok(())
 }
+
+async fn execute_doctor(app: &mut App, checker: &FileChecker) {
+    app.add_log(LogLevel::Info, t!("log.doctor_start"));
+
+    match checker.find_dangling_links().await {
+        Ok(links) if links.is_empty() => {
+            app.add_log(LogLevel::Success, t!("log.doctor_clean"));
+        }
+        Ok(links) => {
+            app.add_log(
+                LogLevel::Warning,
+                &t!(
+                    "log.doctor_found",
+                    links.len()
+                ),
+            );
+            for (link, target) in &links {
+                app.add_log(
+                    LogLevel::Warning,
+                    &t!("log.dangling_link", link.display(), target.display()),
+                );
+            }
+        }
+        Err(e) => {
+            app.add_log(LogLevel::Error, &t!("log.doctor_failed", e));
+        }
+    }
+}
@@ -1,9 +1,17 @@
 use anyhow::{Context, Result};
-use chrono::{DateTime, Datelike, Utc};
-use std::path::PathBuf;
+use chrono::Utc;
+use serde::{Deserialize, Serialize};
+use std::collections::{BTreeMap, HashMap, HashSet};
+use std::path::{Path, PathBuf};
 use tokio::fs;
 
+use crate::file_filter::FileFilter;
 use crate::models::{FileInfo, GroupStats};
+use crate::path_sanitizer::sanitize_component;
+use crate::projection;
+
+/// 保留目录结构时，群组目录内记录原始名称的文件
+const SIDECAR_FILE_NAME: &str = ".qqcleaner.toml";
 
 pub struct Migrator {
     qq_data_dir: PathBuf,
@@ -14,6 +22,7 @@ pub struct MigrateOptions {
     pub target_dir: PathBuf,
     pub keep_structure: bool,  // 保留原始目录结构
     pub delete_after_migrate: bool,  // 迁移后删除原文件
+    pub link_back: bool,  // 迁移后将原文件替换为指向副本的符号链接
 }
 
 impl Default for MigrateOptions {
@@ -30,15 +39,39 @@ impl Default for MigrateOptions {
             target_dir,
             keep_structure: true,
             delete_after_migrate: false,
+            link_back: false,
         }
     }
 }
 
+/// 迁移目标中记录原始群名和文件名的附属文件
+///
+/// 目标路径中的名称都经过 `sanitize_component` 处理，这里保存改写前后的对应关系。
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct GroupSidecar {
+    pub group_id: String,
+    pub group_name: String,
+    #[serde(default)]
+    pub dir_name: Option<String>,
+    #[serde(default)]
+    pub renamed_files: BTreeMap<String, String>,  // 改写后的文件名 -> 原始文件名
+}
+
 #[derive(Debug)]
 pub struct MigrateResult {
     pub migrated_files: usize,
-    pub failed_files: usize,
+    pub failed_files: usize,  // 包括已复制但未能替换为符号链接的文件
+    pub total_size: u64,
+    pub errors: Vec<String>,  // 每个失败文件的原因，由调用方写入日志
+}
+
+#[derive(Debug)]
+pub struct RestoreResult {
+    pub restored_files: usize,
+    pub skipped_files: usize,   // 原位置已存在
+    pub failed_files: usize,    // 复制失败或与数据库记录不符
     pub total_size: u64,
+    pub errors: Vec<String>,    // 每个失败文件的原因，由调用方写入日志
 }
 
 impl Migrator {
@@ -62,58 +95,175 @@ impl Migrator {
         }
     }
 
-    async fn get_file_paths(&self, file: &FileInfo) -> Vec<(PathBuf, PathBuf)> {
+    /// 文件在 QQ 目录中的所有候选路径（原图和缩略图），以及它们在迁移目标中的相对路径
+    fn candidate_paths(&self, file: &FileInfo) -> Vec<(PathBuf, PathBuf)> {
         let mut paths = Vec::new();
 
         if file.file_name.is_empty() {
             return paths;
         }
 
-        let datetime = DateTime::<Utc>::from_timestamp(file.msg_time, 0)
-            .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap());
-
-        let time_dir = format!("{}-{:02}", datetime.year(), datetime.month());
-        let base_dir = self.qq_data_dir.join(&time_dir);
+        let base_dir = self.qq_data_dir.join(file.month_dir());
 
         // Original file
-        let ori_path = base_dir.join("Ori").join(&file.file_name);
-        if ori_path.exists() {
-            paths.push((ori_path, PathBuf::from("Ori").join(&file.file_name)));
-        }
+        paths.push((
+            base_dir.join("Ori").join(&file.file_name),
+            PathBuf::from("Ori").join(sanitize_component(&file.file_name)),
+        ));
 
         // Thumbnail files
         let thumb_filenames = Self::get_thumb_filenames(&file.file_name);
         for thumb_name in thumb_filenames {
-            let thumb_path = base_dir.join("Thumb").join(&thumb_name);
-            if thumb_path.exists() {
-                paths.push((thumb_path, PathBuf::from("Thumb").join(&thumb_name)));
+            paths.push((
+                base_dir.join("Thumb").join(&thumb_name),
+                PathBuf::from("Thumb").join(sanitize_component(&thumb_name)),
+            ));
+        }
+
+        paths
+    }
+
+    /// 需要迁移的文件路径，已经替换为符号链接的文件不再重复迁移
+    async fn get_file_paths(&self, file: &FileInfo) -> Vec<(PathBuf, PathBuf)> {
+        let mut paths = Vec::new();
+
+        for (src_path, rel_path) in self.candidate_paths(file) {
+            if let Ok(metadata) = fs::symlink_metadata(&src_path).await {
+                if metadata.is_file() {
+                    paths.push((src_path, rel_path));
+                }
             }
         }
 
         paths
     }
 
+    /// 用指向迁移副本的符号链接替换原文件
+    ///
+    /// 先在同目录创建临时链接再重命名覆盖，任何一步失败原文件都保持不变。
+    async fn replace_with_link(src_path: &Path, dst_path: &Path) -> Result<()> {
+        let target = fs::canonicalize(dst_path)
+            .await
+            .with_context(|| t!("error.resolve_copy", dst_path.display()))?;
+
+        let mut tmp_name = src_path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".qqcleaner_link");
+        let tmp_path = src_path.with_file_name(tmp_name);
+
+        let _ = fs::remove_file(&tmp_path).await;
+        create_symlink(&target, &tmp_path)
+            .await
+            .with_context(|| t!("error.create_symlink", tmp_path.display()))?;
+
+        if let Err(e) = fs::rename(&tmp_path, src_path).await {
+            let _ = fs::remove_file(&tmp_path).await;
+            return Err(e).with_context(|| t!("error.replace_original", src_path.display()));
+        }
+
+        Ok(())
+    }
+
+    /// 文件在迁移目标中的完整路径
+    fn archive_path(file: &FileInfo, rel_path: &Path, keep_structure: bool, group_dir: &Path) -> PathBuf {
+        if keep_structure {
+            // 保留时间和 Ori/Thumb 结构
+            group_dir.join(file.month_dir()).join(rel_path)
+        } else {
+            // 扁平化存储
+            group_dir.join(rel_path.file_name().unwrap())
+        }
+    }
+
+    /// 群组在迁移目标中的目录名，群号保证不同群组之间不会冲突
+    pub fn group_dir_name(stats: &GroupStats) -> String {
+        format!("{}_{}", sanitize_component(&stats.group_name), stats.group_id)
+    }
+
+    fn sidecar_path(stats: &GroupStats, keep_structure: bool, group_dir: &Path) -> PathBuf {
+        if keep_structure {
+            group_dir.join(SIDECAR_FILE_NAME)
+        } else {
+            // 扁平化存储时所有群组共用目标目录，按群号区分
+            group_dir.join(format!(".qqcleaner_{}.toml", stats.group_id))
+        }
+    }
+
+    /// 写入（或合并已有的）附属文件，记录原始群名和被改写的文件名
+    ///
+    /// 已有的附属文件无法解析时先改名备份再重新写入，返回说明备份位置的提示。
+    async fn write_sidecar(
+        &self,
+        stats: &GroupStats,
+        options: &MigrateOptions,
+        group_dir: &Path,
+        renamed_files: BTreeMap<String, String>,
+    ) -> Result<Option<String>> {
+        let path = Self::sidecar_path(stats, options.keep_structure, group_dir);
+        let mut notice = None;
+
+        let mut sidecar = match fs::read_to_string(&path).await {
+            Ok(content) => match toml::from_str::<GroupSidecar>(&content) {
+                Ok(sidecar) => sidecar,
+                Err(e) => {
+                    // 保留无法解析的文件，其中可能有之前迁移的原始名称
+                    let mut backup_name = path.file_name().unwrap_or_default().to_os_string();
+                    backup_name.push(format!(".corrupt-{}", Utc::now().timestamp()));
+                    let backup = path.with_file_name(backup_name);
+                    fs::rename(&path, &backup)
+                        .await
+                        .with_context(|| t!("error.backup_sidecar", path.display()))?;
+                    notice = Some(t!("error.sidecar_corrupt", path.display(), e.message(), backup.display()));
+                    GroupSidecar::default()
+                }
+            },
+            Err(e) if e.kind() == std::io::ErrorKind::NotFound => GroupSidecar::default(),
+            Err(e) => {
+                return Err(e).with_context(|| t!("error.read_sidecar", path.display()));
+            }
+        };
+
+        sidecar.group_id = stats.group_id.clone();
+        sidecar.group_name = stats.group_name.clone();
+        sidecar.dir_name = if options.keep_structure {
+            Some(Self::group_dir_name(stats))
+        } else {
+            None
+        };
+        sidecar.renamed_files.extend(renamed_files);
+
+        let content = toml::to_string(&sidecar).context(t!("error.serialize_sidecar"))?;
+        fs::write(&path, content)
+            .await
+            .with_context(|| t!("error.write_sidecar", path.display()))?;
+
+        Ok(notice)
+    }
+
     pub async fn migrate_group_files(
         &self,
         stats: &GroupStats,
         options: &MigrateOptions,
+        filter: Option<&FileFilter>,
         progress_callback: Option<Box<dyn Fn(usize, &str) + Send>>,
     ) -> Result<MigrateResult> {
         let mut result = MigrateResult {
             migrated_files: 0,
             failed_files: 0,
             total_size: 0,
+            errors: Vec::new(),
         };
 
         // 创建群组目标目录
         let group_dir = if options.keep_structure {
-            options.target_dir.join(format!("{}_{}", stats.group_name, stats.group_id))
+            options.target_dir.join(Self::group_dir_name(stats))
         } else {
             options.target_dir.clone()
         };
 
         fs::create_dir_all(&group_dir).await
-            .context("创建目标目录失败")?;
+            .context(t!("error.create_target_dir"))?;
+
+        let mut renamed_files = BTreeMap::new();
 
         for (idx, file) in stats.files.iter().enumerate() {
             if let Some(ref callback) = progress_callback {
@@ -124,24 +274,30 @@ impl Migrator {
                 continue;
             }
 
+            if let Some(filter) = filter {
+                if !filter.matches(file) {
+                    continue;
+                }
+            }
+
             let file_paths = self.get_file_paths(file).await;
 
             for (src_path, rel_path) in file_paths {
-                let dst_path = if options.keep_structure {
-                    // 保留时间和 Ori/Thumb 结构
-                    let datetime = DateTime::<Utc>::from_timestamp(file.msg_time, 0)
-                        .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap());
-                    let time_dir = format!("{}-{:02}", datetime.year(), datetime.month());
-                    group_dir.join(time_dir).join(rel_path)
-                } else {
-                    // 扁平化存储
-                    group_dir.join(src_path.file_name().unwrap())
-                };
+                if let (Some(original), Some(renamed)) = (src_path.file_name(), rel_path.file_name()) {
+                    if original != renamed {
+                        renamed_files.insert(
+                            renamed.to_string_lossy().into_owned(),
+                            original.to_string_lossy().into_owned(),
+                        );
+                    }
+                }
+
+                let dst_path = Self::archive_path(file, &rel_path, options.keep_structure, &group_dir);
 
                 // 创建父目录
                 if let Some(parent) = dst_path.parent() {
                     if let Err(e) = fs::create_dir_all(parent).await {
-                        eprintln!("创建目录失败: {:?}, 错误: {}", parent, e);
+                        result.errors.push(t!("error.create_dir", parent.display(), e));
                         result.failed_files += 1;
                         continue;
                     }
@@ -150,16 +306,230 @@ impl Migrator {
                 // 复制文件
                 match fs::copy(&src_path, &dst_path).await {
                     Ok(size) => {
+                        if options.link_back {
+                            // 副本已经写入目标，但原文件没有替换成链接，仍然占用本地空间
+                            if let Err(e) = Self::replace_with_link(&src_path, &dst_path).await {
+                                result.errors.push(t!("error.link_back_failed", dst_path.display(), format!("{:#}", e)));
+                                result.failed_files += 1;
+                                continue;
+                            }
+                        } else if options.delete_after_migrate {
+                            // 如果设置了删除原文件
+                            let _ = fs::remove_file(&src_path).await;
+                        }
+
                         result.total_size += size;
                         result.migrated_files += 1;
+                    }
+                    Err(e) => {
+                        result.errors.push(t!("error.copy_file", src_path.display(), dst_path.display(), e));
+                        result.failed_files += 1;
+                    }
+                }
+            }
+        }
 
-                        // 如果设置了删除原文件
-                        if options.delete_after_migrate {
-                            let _ = fs::remove_file(&src_path).await;
-                        }
+        if let Some(notice) = self.write_sidecar(stats, options, &group_dir, renamed_files).await? {
+            result.errors.push(notice);
+        }
+
+        Ok(result)
+    }
+
+    /// 复制文件到目标位置；目标是符号链接时先复制到临时文件再覆盖，保证链接不会丢失
+    async fn copy_into_place(src_path: &Path, dst_path: &Path, replace: bool) -> std::io::Result<u64> {
+        if !replace {
+            return fs::copy(src_path, dst_path).await;
+        }
+
+        let mut tmp_name = dst_path.file_name().unwrap_or_default().to_os_string();
+        tmp_name.push(".qqcleaner_restore");
+        let tmp_path = dst_path.with_file_name(tmp_name);
+
+        let size = fs::copy(src_path, &tmp_path).await?;
+        if let Err(e) = fs::rename(&tmp_path, dst_path).await {
+            let _ = fs::remove_file(&tmp_path).await;
+            return Err(e);
+        }
+
+        Ok(size)
+    }
+
+    /// 查找群组在迁移目标中的归档，返回 (目录, 是否保留目录结构)
+    ///
+    /// 两种布局由附属文件的位置区分：保留结构时在群组目录内，扁平化存储时在目标目录中按群号命名。
+    /// 同一目标可能先后用两种布局迁移过，保留结构的归档排在前面。
+    async fn find_archives(&self, stats: &GroupStats, target_dir: &Path) -> Vec<(PathBuf, bool)> {
+        let mut archives = Vec::new();
+
+        if let Some(group_dir) = self.find_group_dir(stats, target_dir).await {
+            archives.push((group_dir, true));
+        }
+
+        let flat_sidecar = Self::sidecar_path(stats, false, target_dir);
+        if fs::try_exists(&flat_sidecar).await.unwrap_or(false) {
+            archives.push((target_dir.to_path_buf(), false));
+        }
+
+        archives
+    }
+
+    /// 查找群组在保留目录结构的迁移目标中的目录
+    ///
+    /// 优先使用按当前群名计算的目录；群名改过时，通过附属文件或目录名末尾的群号匹配。
+    async fn find_group_dir(&self, stats: &GroupStats, target_dir: &Path) -> Option<PathBuf> {
+        let expected = target_dir.join(Self::group_dir_name(stats));
+        if expected.is_dir() {
+            return Some(expected);
+        }
+
+        let suffix = format!("_{}", stats.group_id);
+        let mut entries = fs::read_dir(target_dir).await.ok()?;
+        while let Ok(Some(entry)) = entries.next_entry().await {
+            let path = entry.path();
+            if !path.is_dir() {
+                continue;
+            }
+
+            if let Ok(content) = fs::read_to_string(path.join(SIDECAR_FILE_NAME)).await {
+                if let Ok(sidecar) = toml::from_str::<GroupSidecar>(&content) {
+                    if sidecar.group_id == stats.group_id {
+                        return Some(path);
+                    }
+                    continue;
+                }
+            }
+
+            if entry.file_name().to_string_lossy().ends_with(&suffix) {
+                return Some(path);
+            }
+        }
+
+        None
+    }
+
+    /// 将迁移目标中的文件复制回 QQ 的 `<YYYY-MM>/Ori|Thumb` 目录
+    ///
+    /// 归档的布局从附属文件判断。以数据库记录为准逐个查找归档文件：原位置已存在的跳过，
+    /// 原图大小与记录不符的视为失败，不会覆盖任何已有文件。缩略图没有可核对的大小，
+    /// 只在同一条记录的原图通过校验、或归档中没有原图且不会与其他记录混淆时恢复。
+    pub async fn restore_group_files(
+        &self,
+        stats: &GroupStats,
+        target_dir: &Path,
+        filter: Option<&FileFilter>,
+    ) -> Result<RestoreResult> {
+        let mut result = RestoreResult {
+            restored_files: 0,
+            skipped_files: 0,
+            failed_files: 0,
+            total_size: 0,
+            errors: Vec::new(),
+        };
+
+        let archives = self.find_archives(stats, target_dir).await;
+        if archives.is_empty() {
+            return Ok(result);
+        }
+
+        // 扁平化存储丢失了月份目录，同名文件出现在多个月份时无法确定缩略图属于哪条记录
+        let mut months_by_name: HashMap<&str, HashSet<String>> = HashMap::new();
+        for file in &stats.files {
+            let (month, name) = projection::disk_key(file);
+            months_by_name.entry(name).or_default().insert(month);
+        }
+
+        for file in &stats.files {
+            if let Some(filter) = filter {
+                if !filter.matches(file) {
+                    continue;
+                }
+            }
+
+            let candidates = self.candidate_paths(file);
+            let mut found = None;
+            for (group_dir, keep_structure) in &archives {
+                let archived: Vec<PathBuf> = candidates
+                    .iter()
+                    .map(|(_, rel_path)| Self::archive_path(file, rel_path, *keep_structure, group_dir))
+                    .collect();
+                if archived.iter().any(|path| path.exists()) {
+                    found = Some((archived, *keep_structure));
+                    break;
+                }
+            }
+            let Some((archived_paths, keep_structure)) = found else {
+                continue;
+            };
+
+            // 原图大小必须与数据库记录一致，避免扁平化存储下同名文件被错误恢复
+            let ori_archived = &archived_paths[0];
+            let ori_state = match fs::metadata(ori_archived).await {
+                Ok(meta) if file.file_size > 0 && meta.len() != file.file_size as u64 => {
+                    result.errors.push(t!(
+                        "error.size_mismatch",
+                        ori_archived.display(),
+                        meta.len(),
+                        file.file_size
+                    ));
+                    Some(false)
+                }
+                Ok(_) => Some(true),
+                Err(_) => None,
+            };
+            let thumbs_verified = match ori_state {
+                Some(verified) => verified,
+                None => {
+                    keep_structure
+                        || months_by_name
+                            .get(file.file_name.as_str())
+                            .is_none_or(|months| months.len() <= 1)
+                }
+            };
+
+            for ((qq_path, rel_path), archived_path) in candidates.iter().zip(&archived_paths) {
+                let Ok(_) = fs::metadata(archived_path).await else {
+                    continue;
+                };
+
+                // 符号链接模式留下的链接需要替换成真实文件，其他已存在的文件跳过
+                let replaces_link = match fs::symlink_metadata(qq_path).await {
+                    Ok(metadata) if metadata.file_type().is_symlink() => true,
+                    Ok(_) => {
+                        result.skipped_files += 1;
+                        continue;
+                    }
+                    Err(_) => false,
+                };
+
+                let verified = if rel_path.starts_with("Ori") {
+                    ori_state != Some(false)
+                } else {
+                    thumbs_verified
+                };
+                if !verified {
+                    if !rel_path.starts_with("Ori") {
+                        result.errors.push(t!("error.thumb_unverified", archived_path.display()));
+                    }
+                    result.failed_files += 1;
+                    continue;
+                }
+
+                if let Some(parent) = qq_path.parent() {
+                    if let Err(e) = fs::create_dir_all(parent).await {
+                        result.errors.push(t!("error.create_dir", parent.display(), e));
+                        result.failed_files += 1;
+                        continue;
+                    }
+                }
+
+                match Self::copy_into_place(archived_path, qq_path, replaces_link).await {
+                    Ok(size) => {
+                        result.total_size += size;
+                        result.restored_files += 1;
                     }
                     Err(e) => {
-                        eprintln!("复制文件失败: {:?} -> {:?}, 错误: {}", src_path, dst_path, e);
+                        result.errors.push(t!("error.restore_file", archived_path.display(), qq_path.display(), e));
                         result.failed_files += 1;
                     }
                 }
@@ -170,3 +540,191 @@ impl Migrator {
     }
 }
 
+#[cfg(unix)]
+async fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    fs::symlink(target, link).await
+}
+
+#[cfg(windows)]
+async fn create_symlink(target: &Path, link: &Path) -> std::io::Result<()> {
+    fs::symlink_file(target, link).await
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const NOV_2023: i64 = 1_700_000_000;
+    const DEC_2023: i64 = 1_702_000_000;
+
+    /// 临时的 QQ 图片目录和迁移目标，结束时删除
+    struct Dirs {
+        root: PathBuf,
+        qq: PathBuf,
+        target: PathBuf,
+    }
+
+    impl Dirs {
+        fn new(name: &str) -> Self {
+            let root = std::env::temp_dir().join(format!("qqcleaner_migrator_{}_{}", name, std::process::id()));
+            let _ = std::fs::remove_dir_all(&root);
+            let dirs = Dirs {
+                qq: root.join("Pic"),
+                target: root.join("archive"),
+                root,
+            };
+            std::fs::create_dir_all(&dirs.qq).unwrap();
+            std::fs::create_dir_all(&dirs.target).unwrap();
+            dirs
+        }
+
+        fn migrator(&self) -> Migrator {
+            Migrator::new(self.qq.clone())
+        }
+
+        fn options(&self, keep_structure: bool) -> MigrateOptions {
+            MigrateOptions {
+                target_dir: self.target.clone(),
+                keep_structure,
+                delete_after_migrate: true,
+                link_back: false,
+            }
+        }
+
+        fn size_in_qq(&self, rel_path: &str) -> Option<u64> {
+            std::fs::metadata(self.qq.join(rel_path)).ok().map(|meta| meta.len())
+        }
+    }
+
+    impl Drop for Dirs {
+        fn drop(&mut self) {
+            let _ = std::fs::remove_dir_all(&self.root);
+        }
+    }
+
+    /// 写入指定大小的文件，自动创建父目录
+    fn put(path: &Path, size: usize) {
+        std::fs::create_dir_all(path.parent().unwrap()).unwrap();
+        std::fs::write(path, vec![b'x'; size]).unwrap();
+    }
+
+    fn single_file_group() -> GroupStats {
+        GroupStats::test("1", vec![FileInfo::test(1, "a.jpg", NOV_2023, 100)])
+    }
+
+    #[tokio::test]
+    async fn migrated_files_restore_from_either_layout() {
+        for keep_structure in [true, false] {
+            let dirs = Dirs::new(if keep_structure { "structured" } else { "flat" });
+            put(&dirs.qq.join("2023-11/Ori/a.jpg"), 100);
+            put(&dirs.qq.join("2023-11/Thumb/a_0.jpg"), 10);
+            let stats = single_file_group();
+
+            let migrated = dirs
+                .migrator()
+                .migrate_group_files(&stats, &dirs.options(keep_structure), None, None)
+                .await
+                .unwrap();
+            assert_eq!(migrated.migrated_files, 2);
+            assert_eq!(dirs.size_in_qq("2023-11/Ori/a.jpg"), None);
+
+            let restored = dirs.migrator().restore_group_files(&stats, &dirs.target, None).await.unwrap();
+            assert_eq!((restored.restored_files, restored.failed_files), (2, 0));
+            assert_eq!(dirs.size_in_qq("2023-11/Ori/a.jpg"), Some(100));
+            assert_eq!(dirs.size_in_qq("2023-11/Thumb/a_0.jpg"), Some(10));
+        }
+    }
+
+    #[tokio::test]
+    async fn renamed_group_is_found_through_its_sidecar() {
+        let dirs = Dirs::new("renamed");
+        put(&dirs.qq.join("2023-11/Ori/a.jpg"), 100);
+        let stats = single_file_group();
+        dirs.migrator()
+            .migrate_group_files(&stats, &dirs.options(true), None, None)
+            .await
+            .unwrap();
+
+        let mut renamed = single_file_group();
+        renamed.group_name = "新群名".to_string();
+        let restored = dirs.migrator().restore_group_files(&renamed, &dirs.target, None).await.unwrap();
+        assert_eq!(restored.restored_files, 1);
+        assert_eq!(dirs.size_in_qq("2023-11/Ori/a.jpg"), Some(100));
+    }
+
+    #[tokio::test]
+    async fn flat_files_without_a_sidecar_are_not_restored() {
+        let dirs = Dirs::new("no_sidecar");
+        put(&dirs.target.join("a.jpg"), 100);
+
+        let restored = dirs
+            .migrator()
+            .restore_group_files(&single_file_group(), &dirs.target, None)
+            .await
+            .unwrap();
+        assert_eq!(restored.restored_files, 0);
+        assert_eq!(dirs.size_in_qq("2023-11/Ori/a.jpg"), None);
+    }
+
+    #[tokio::test]
+    async fn size_mismatch_blocks_the_original_and_its_thumbnails() {
+        let dirs = Dirs::new("mismatch");
+        // 扁平化归档中的同名文件属于别的记录
+        put(&dirs.target.join(".qqcleaner_1.toml"), 0);
+        put(&dirs.target.join("a.jpg"), 50);
+        put(&dirs.target.join("a_0.jpg"), 5);
+
+        let restored = dirs
+            .migrator()
+            .restore_group_files(&single_file_group(), &dirs.target, None)
+            .await
+            .unwrap();
+        assert_eq!((restored.restored_files, restored.failed_files), (0, 2));
+        assert!(!dirs.qq.join("2023-11").exists());
+    }
+
+    #[tokio::test]
+    async fn flat_thumbnails_are_restored_only_when_unambiguous() {
+        let dirs = Dirs::new("ambiguous");
+        put(&dirs.target.join(".qqcleaner_1.toml"), 0);
+        put(&dirs.target.join("a_0.jpg"), 5);
+
+        // 同名文件出现在两个月份，无法确定缩略图属于哪条记录
+        let stats = GroupStats::test(
+            "1",
+            vec![
+                FileInfo::test(1, "a.jpg", NOV_2023, 100),
+                FileInfo::test(2, "a.jpg", DEC_2023, 100),
+            ],
+        );
+        let restored = dirs.migrator().restore_group_files(&stats, &dirs.target, None).await.unwrap();
+        assert_eq!((restored.restored_files, restored.failed_files), (0, 2));
+        assert_eq!(dirs.size_in_qq("2023-11/Thumb/a_0.jpg"), None);
+        assert_eq!(dirs.size_in_qq("2023-12/Thumb/a_0.jpg"), None);
+
+        let restored = dirs
+            .migrator()
+            .restore_group_files(&single_file_group(), &dirs.target, None)
+            .await
+            .unwrap();
+        assert_eq!(restored.restored_files, 1);
+        assert_eq!(dirs.size_in_qq("2023-11/Thumb/a_0.jpg"), Some(5));
+    }
+
+    #[tokio::test]
+    async fn existing_files_are_not_overwritten() {
+        let dirs = Dirs::new("existing");
+        put(&dirs.qq.join("2023-11/Ori/a.jpg"), 100);
+        let stats = single_file_group();
+        let options = MigrateOptions {
+            delete_after_migrate: false,
+            ..dirs.options(true)
+        };
+        dirs.migrator().migrate_group_files(&stats, &options, None, None).await.unwrap();
+        put(&dirs.qq.join("2023-11/Ori/a.jpg"), 1);
+
+        let restored = dirs.migrator().restore_group_files(&stats, &dirs.target, None).await.unwrap();
+        assert_eq!((restored.restored_files, restored.skipped_files), (0, 1));
+        assert_eq!(dirs.size_in_qq("2023-11/Ori/a.jpg"), Some(1));
+    }
+}
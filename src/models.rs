@@ -1,3 +1,5 @@
+use chrono::{DateTime, Datelike, Utc};
+
 /// 文件信息
 #[derive(Debug, Clone)]
 #[allow(dead_code)]
@@ -17,6 +19,92 @@ pub struct FileInfo {
     pub msg_time: i64,          // 40050
     pub original: i64,          // 82302
     pub actual_size: Option<u64>, // 文件系统实际大小（如果文件存在）
+    pub linked_size: u64,       // actual_size 中通过符号链接指向迁移目录的部分
+    pub ori_linked_size: u64,   // linked_size 中属于原图的部分
+    pub ori_size: Option<u64>,  // 原图大小（如果存在）
+    pub thumb_size: Option<u64>, // 缩略图 _0/_720 的合计大小（如果存在）
+}
+
+impl FileInfo {
+    /// 在群组内唯一标识一条文件记录，同一条消息中的多张图片文件名不同
+    pub fn key(&self) -> String {
+        format!("{}:{}", self.msg_id, self.file_name)
+    }
+
+    /// 文件所在的月份目录，原图和缩略图分别在其中的 Ori 和 Thumb 下
+    pub fn month_dir(&self) -> String {
+        month_dir(self.msg_time)
+    }
+
+    /// 发送时勾选了"原图"，没有经过压缩
+    pub fn is_original(&self) -> bool {
+        self.original != 0
+    }
+
+    /// 文件名中的扩展名（不带点）
+    pub fn extension(&self) -> Option<&str> {
+        self.file_name.rsplit_once('.').map(|(_, ext)| ext)
+    }
+
+    pub fn is_gif(&self) -> bool {
+        self.extension().is_some_and(|ext| ext.eq_ignore_ascii_case("gif"))
+    }
+
+    pub fn media_type(&self) -> MediaType {
+        if self.sub_element_type == 1 {
+            MediaType::Sticker
+        } else if self.is_gif() {
+            MediaType::Gif
+        } else {
+            MediaType::Image
+        }
+    }
+}
+
+/// 图片的媒体类型
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum MediaType {
+    Image,      // 普通图片
+    Gif,        // 动图
+    Sticker,    // 表情（45003 子类型为 1）
+}
+
+impl MediaType {
+    pub fn description(&self) -> &'static str {
+        match self {
+            MediaType::Image => t!("media.image"),
+            MediaType::Gif => t!("media.gif"),
+            MediaType::Sticker => t!("media.sticker"),
+        }
+    }
+}
+
+/// 清理时删除文件的哪些部分
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CleanScope {
+    Both,       // 原图和缩略图
+    OriOnly,    // 只删除 Ori 下的原图
+    ThumbOnly,  // 只删除 Thumb 下的 _0/_720 缩略图
+}
+
+impl CleanScope {
+    pub const ALL: [CleanScope; 3] = [CleanScope::Both, CleanScope::OriOnly, CleanScope::ThumbOnly];
+
+    pub fn includes_ori(&self) -> bool {
+        matches!(self, CleanScope::Both | CleanScope::OriOnly)
+    }
+
+    pub fn includes_thumb(&self) -> bool {
+        matches!(self, CleanScope::Both | CleanScope::ThumbOnly)
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            CleanScope::Both => t!("clean_scope.both"),
+            CleanScope::OriOnly => t!("clean_scope.ori_only"),
+            CleanScope::ThumbOnly => t!("clean_scope.thumb_only"),
+        }
+    }
 }
 
 /// 群组详细信息
@@ -40,10 +128,38 @@ pub struct GroupStats {
     pub group_id: String,
     pub group_name: String,
     pub total_size: u64,        // 总大小（字节）
+    pub linked_size: u64,       // 其中位于符号链接另一端的大小（不占本地空间）
     pub file_count: usize,      // 文件数量
     pub exist_count: usize,     // 存在的文件数量
     pub missing_count: usize,   // 缺失(已清理)的文件数量
     pub files: Vec<FileInfo>,
+    pub info: Option<GroupInfo>, // group_info 数据库中的群资料（可能缺失）
+}
+
+impl GroupStats {
+    pub fn group_remark(&self) -> Option<&str> {
+        self.info
+            .as_ref()
+            .and_then(|info| info.group_remark.as_deref())
+            .filter(|remark| !remark.is_empty())
+    }
+
+    /// 最后一条图片消息的时间，没有文件时为 0
+    pub fn last_activity(&self) -> i64 {
+        self.files.iter().map(|f| f.msg_time).max().unwrap_or(0)
+    }
+
+    /// 已经退出的群（group_info 中 quit_flag 非 0），缺少群资料时视为仍在群中
+    pub fn has_quit(&self) -> bool {
+        self.info.as_ref().is_some_and(|info| info.quit_flag != 0)
+    }
+}
+
+/// 消息时间对应的 Pic 月份目录名（按 UTC），如 "2024-03"
+pub fn month_dir(msg_time: i64) -> String {
+    let datetime = DateTime::<Utc>::from_timestamp(msg_time, 0)
+        .unwrap_or_else(|| DateTime::<Utc>::from_timestamp(0, 0).unwrap());
+    format!("{}-{:02}", datetime.year(), datetime.month())
 }
 
 /// 格式化字节大小
@@ -62,3 +178,133 @@ pub fn format_bytes(bytes: u64) -> String {
         format!("{} B", bytes)
     }
 }
+
+/// 用能整除的最大单位表示大小，如 "100MB"、"1500KB"，`parse_size` 解析后与原值相同
+pub fn format_size_exact(bytes: u64) -> String {
+    const UNITS: [(&str, u64); 4] = [
+        ("TB", 1024 * 1024 * 1024 * 1024),
+        ("GB", 1024 * 1024 * 1024),
+        ("MB", 1024 * 1024),
+        ("KB", 1024),
+    ];
+
+    UNITS
+        .iter()
+        .find(|(_, size)| bytes > 0 && bytes.is_multiple_of(*size))
+        .map(|(unit, size)| format!("{}{}", bytes / size, unit))
+        .unwrap_or_else(|| bytes.to_string())
+}
+
+/// 解析带单位的大小，如 "500MB"、"2G"、"1.5 GiB"，不带单位时按字节计算
+pub fn parse_size(input: &str) -> Option<u64> {
+    let input = input.trim();
+    let split = input
+        .find(|c: char| !(c.is_ascii_digit() || c == '.'))
+        .unwrap_or(input.len());
+    let (number, unit) = input.split_at(split);
+    let number: f64 = number.parse().ok()?;
+
+    let multiplier: u64 = match unit.trim().to_ascii_uppercase().as_str() {
+        "" | "B" => 1,
+        "K" | "KB" | "KIB" => 1024,
+        "M" | "MB" | "MIB" => 1024 * 1024,
+        "G" | "GB" | "GIB" => 1024 * 1024 * 1024,
+        "T" | "TB" | "TIB" => 1024 * 1024 * 1024 * 1024,
+        _ => return None,
+    };
+
+    Some((number * multiplier as f64) as u64)
+}
+
+#[cfg(test)]
+impl FileInfo {
+    /// 测试用的现存文件记录，只有原图
+    pub fn test(msg_id: i64, file_name: &str, msg_time: i64, size: u64) -> Self {
+        FileInfo {
+            client_seq: 0,
+            msg_random: 0,
+            msg_id,
+            filepath: String::new(),
+            thumbpath: String::new(),
+            nt_uid: String::new(),
+            peer_uid: String::new(),
+            chat_type: 2,
+            element_type: 2,
+            sub_element_type: 0,
+            file_name: file_name.to_string(),
+            file_size: size as i64,
+            msg_time,
+            original: 0,
+            actual_size: Some(size),
+            linked_size: 0,
+            ori_linked_size: 0,
+            ori_size: Some(size),
+            thumb_size: None,
+        }
+    }
+
+    /// 加上缩略图，actual_size 同时增加
+    pub fn with_thumb(mut self, size: u64) -> Self {
+        self.thumb_size = Some(size);
+        self.actual_size = Some(self.actual_size.unwrap_or(0) + size);
+        self
+    }
+}
+
+#[cfg(test)]
+impl GroupStats {
+    pub fn test(group_id: &str, files: Vec<FileInfo>) -> Self {
+        GroupStats {
+            group_id: group_id.to_string(),
+            group_name: format!("群 {}", group_id),
+            total_size: files.iter().filter_map(|f| f.actual_size).sum(),
+            linked_size: files.iter().map(|f| f.linked_size).sum(),
+            file_count: files.len(),
+            exist_count: files.iter().filter(|f| f.actual_size.is_some()).count(),
+            missing_count: files.iter().filter(|f| f.actual_size.is_none()).count(),
+            files,
+            info: None,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const MB: u64 = 1024 * 1024;
+
+    #[test]
+    fn parses_sizes_with_units() {
+        assert_eq!(parse_size("512"), Some(512));
+        assert_eq!(parse_size("10KB"), Some(10 * 1024));
+        assert_eq!(parse_size("500mb"), Some(500 * MB));
+        assert_eq!(parse_size("2G"), Some(2 * 1024 * MB));
+        assert_eq!(parse_size("1.5 GiB"), Some(1536 * MB));
+        assert_eq!(parse_size(" 3 MB "), Some(3 * MB));
+    }
+
+    #[test]
+    fn rejects_invalid_sizes() {
+        assert_eq!(parse_size(""), None);
+        assert_eq!(parse_size("MB"), None);
+        assert_eq!(parse_size("5 apples"), None);
+        assert_eq!(parse_size("-5MB"), None);
+    }
+
+    #[test]
+    fn exact_format_round_trips() {
+        for bytes in [1, 1023, 1024, 1536000, 100 * MB, 1024 * MB + 1, 3 * 1024 * 1024 * MB] {
+            assert_eq!(parse_size(&format_size_exact(bytes)), Some(bytes), "{}", bytes);
+        }
+        assert_eq!(format_size_exact(1536000), "1500KB");
+        assert_eq!(format_size_exact(100 * MB), "100MB");
+    }
+
+    #[test]
+    fn month_dir_uses_utc() {
+        // 2024-03-31 23:30:00 UTC
+        assert_eq!(month_dir(1711927800), "2024-03");
+        assert_eq!(month_dir(0), "1970-01");
+    }
+}
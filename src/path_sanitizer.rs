@@ -0,0 +1,149 @@
+/// 单个路径组件允许的最大字节数（常见文件系统上限为 255，留出后缀余量）
+const MAX_COMPONENT_BYTES: usize = 120;
+
+/// 追加在被改写名称之后的分隔符，未改写的名称中不会出现该字符
+const HASH_SEPARATOR: char = '~';
+
+/// Windows 保留的设备名，不能直接作为文件或目录名
+const RESERVED_NAMES: [&str; 22] = [
+    "CON", "PRN", "AUX", "NUL", "COM1", "COM2", "COM3", "COM4", "COM5", "COM6", "COM7", "COM8",
+    "COM9", "LPT1", "LPT2", "LPT3", "LPT4", "LPT5", "LPT6", "LPT7", "LPT8", "LPT9",
+];
+
+/// 将任意字符串转换为可安全使用的单个路径组件
+///
+/// 规则是确定性的：不需要改写的名称原样返回；一旦发生替换或截断，
+/// 结果末尾会追加 `~` 加原始名称的哈希。`~` 本身也会被替换，
+/// 所以原样返回的名称与改写后的名称不会相互冲突。
+pub fn sanitize_component(name: &str) -> String {
+    let mut cleaned: String = name.chars().map(replace_unsafe_char).collect();
+
+    // 首尾的空格和点在 Windows / SMB 上会被静默丢弃
+    cleaned = cleaned.trim_matches(|c| c == ' ' || c == '.').to_string();
+
+    if cleaned.is_empty() {
+        cleaned.push('_');
+    }
+
+    let stem = cleaned.split('.').next().unwrap_or("");
+    if RESERVED_NAMES
+        .iter()
+        .any(|reserved| reserved.eq_ignore_ascii_case(stem))
+    {
+        cleaned.insert(0, '_');
+    }
+
+    truncate_to_bytes(&mut cleaned, MAX_COMPONENT_BYTES);
+
+    if cleaned == name {
+        cleaned
+    } else {
+        format!("{}{}{:016x}", cleaned, HASH_SEPARATOR, fnv1a_64(name.as_bytes()))
+    }
+}
+
+fn replace_unsafe_char(c: char) -> char {
+    match c {
+        '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' | HASH_SEPARATOR => '_',
+        c if c.is_control() => '_',
+        // 零宽字符、变体选择符和组合用的按键符号（常见于 emoji 组合序列）
+        '\u{200B}'..='\u{200F}' | '\u{20E3}' | '\u{FE00}'..='\u{FE0F}' | '\u{FEFF}' => '_',
+        // emoji 在部分文件系统和 NAS 上无法存储；扩展区汉字（如 𠮷）不受影响
+        '\u{2600}'..='\u{27BF}' | '\u{2B00}'..='\u{2BFF}' | '\u{1F000}'..='\u{1FAFF}' | '\u{E0000}'..='\u{E007F}' => '_',
+        c => c,
+    }
+}
+
+fn truncate_to_bytes(s: &mut String, max_bytes: usize) {
+    if s.len() <= max_bytes {
+        return;
+    }
+    let mut end = max_bytes;
+    while !s.is_char_boundary(end) {
+        end -= 1;
+    }
+    s.truncate(end);
+}
+
+/// FNV-1a 哈希，跨平台、跨版本保持稳定
+fn fnv1a_64(bytes: &[u8]) -> u64 {
+    const OFFSET_BASIS: u64 = 0xcbf2_9ce4_8422_2325;
+    const PRIME: u64 = 0x0000_0100_0000_01b3;
+
+    bytes.iter().fold(OFFSET_BASIS, |hash, &b| {
+        (hash ^ b as u64).wrapping_mul(PRIME)
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 改写后名称中 `~` 之前的部分
+    fn cleaned_part(name: &str) -> &str {
+        name.split_once(HASH_SEPARATOR).map(|(cleaned, _)| cleaned).unwrap_or(name)
+    }
+
+    #[test]
+    fn safe_names_are_unchanged() {
+        for name in ["123456", "摄影交流群", "IMG_0001.jpg", "a b.c", "{ABC-123}.png"] {
+            assert_eq!(sanitize_component(name), name);
+        }
+    }
+
+    #[test]
+    fn unsafe_names_are_rewritten_with_a_hash() {
+        assert_eq!(
+            sanitize_component("../etc/passwd"),
+            format!("_etc_passwd~{:016x}", fnv1a_64(b"../etc/passwd"))
+        );
+        assert_eq!(sanitize_component(".."), format!("_~{:016x}", fnv1a_64(b"..")));
+        assert_eq!(cleaned_part(&sanitize_component("a\nb\tc\u{7f}")), "a_b_c_");
+        assert_eq!(cleaned_part(&sanitize_component("a:b*c?")), "a_b_c_");
+        assert_eq!(cleaned_part(&sanitize_component(" 群名. ")), "群名");
+        assert_eq!(cleaned_part(&sanitize_component("群\u{200B}名")), "群_名");
+    }
+
+    #[test]
+    fn emoji_are_replaced_but_supplementary_han_is_kept() {
+        assert_eq!(sanitize_component("𠮷野家"), "𠮷野家");
+        assert_eq!(cleaned_part(&sanitize_component("相亲相爱❤️一家人")), "相亲相爱__一家人");
+        assert_eq!(cleaned_part(&sanitize_component("猫猫🐱群")), "猫猫_群");
+        assert_eq!(cleaned_part(&sanitize_component("⭐星标")), "_星标");
+    }
+
+    #[test]
+    fn reserved_device_names_are_prefixed() {
+        assert_eq!(cleaned_part(&sanitize_component("CON.txt")), "_CON.txt");
+        assert_eq!(cleaned_part(&sanitize_component("lpt1")), "_lpt1");
+        assert_eq!(sanitize_component("CONSOLE.txt"), "CONSOLE.txt");
+    }
+
+    #[test]
+    fn long_names_are_cut_at_a_char_boundary() {
+        let name = "群".repeat(50);
+        assert_eq!(cleaned_part(&sanitize_component(&name)), "群".repeat(40));
+
+        // 120 字节落在多字节字符中间时向前退到字符边界
+        let name = format!("a{}", "群".repeat(50));
+        let cleaned = sanitize_component(&name);
+        assert_eq!(cleaned_part(&cleaned), format!("a{}", "群".repeat(39)));
+    }
+
+    #[test]
+    fn rewritten_names_are_deterministic_and_distinct() {
+        assert_eq!(sanitize_component("a/b"), sanitize_component("a/b"));
+        // 清理后相同的名称由哈希区分
+        assert_ne!(sanitize_component("a/b"), sanitize_component("a:b"));
+        assert_ne!(sanitize_component("a/b"), sanitize_component("a_b"));
+    }
+
+    #[test]
+    fn names_with_tilde_cannot_collide_with_hashed_names() {
+        let hashed = sanitize_component("a/b");
+        // 与改写结果字面相同的原始名称也会被改写，不会占用同一个路径
+        assert_ne!(sanitize_component(&hashed), hashed);
+        assert_eq!(cleaned_part(&sanitize_component("a~b")), "a_b");
+        assert_ne!(sanitize_component("a~b"), "a~b");
+    }
+}
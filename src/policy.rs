@@ -0,0 +1,311 @@
+use std::cmp::Reverse;
+use std::collections::{HashMap, HashSet};
+
+use crate::file_filter::FileFilter;
+use crate::models::{format_bytes, CleanScope, FileInfo, GroupStats};
+use crate::projection;
+
+/// 每个群单独计算的清理策略
+///
+/// 在时间范围等文件条件之外，按 msg_time 从新到旧决定每个群保留哪些文件。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum GroupPolicy {
+    None,              // 只按文件条件清理
+    KeepNewest(usize), // 每个群保留最新的 N 个文件
+    Quota(u64),        // 每个群最多保留这么多字节，从最旧的文件开始删除
+}
+
+impl GroupPolicy {
+    /// 按 `P` 键循环的预设策略，自定义值的下一个是不限制
+    pub fn next_preset(&self) -> GroupPolicy {
+        const MB: u64 = 1024 * 1024;
+        match self {
+            GroupPolicy::None => GroupPolicy::KeepNewest(100),
+            GroupPolicy::KeepNewest(100) => GroupPolicy::KeepNewest(1000),
+            GroupPolicy::KeepNewest(1000) => GroupPolicy::Quota(100 * MB),
+            GroupPolicy::Quota(size) if *size == 100 * MB => GroupPolicy::Quota(1024 * MB),
+            _ => GroupPolicy::None,
+        }
+    }
+
+    pub fn description(&self) -> String {
+        match self {
+            GroupPolicy::None => t!("policy.none").to_string(),
+            GroupPolicy::KeepNewest(count) => t!("policy.keep_newest", count),
+            GroupPolicy::Quota(size) => t!("policy.quota", format_bytes(*size)),
+        }
+    }
+
+    /// 按策略需要删除的文件（`FileInfo::key`），`None` 表示策略不限制
+    ///
+    /// 只考虑仍然存在的文件。保留名额从最新的文件开始分配，与文件条件无关；
+    /// 超出名额的文件中只有符合 `filter` 的才会删除，因此设置了其他条件时群可能仍超出配额。
+    pub fn select(&self, stat: &GroupStats, filter: &FileFilter) -> Option<HashSet<String>> {
+        let mut files: Vec<&FileInfo> = stat
+            .files
+            .iter()
+            .filter(|f| f.actual_size.is_some())
+            .collect();
+        files.sort_by_key(|f| Reverse(f.msg_time));
+
+        let keep = match *self {
+            GroupPolicy::None => return None,
+            GroupPolicy::KeepNewest(count) => count.min(files.len()),
+            GroupPolicy::Quota(limit) => {
+                let mut used = 0u64;
+                files
+                    .iter()
+                    .take_while(|f| {
+                        used += f.actual_size.unwrap_or(0);
+                        used <= limit
+                    })
+                    .count()
+            }
+        };
+
+        Some(
+            files[keep..]
+                .iter()
+                .filter(|f| filter.matches(f))
+                .map(|f| f.key())
+                .collect(),
+        )
+    }
+}
+
+/// 全局存储预算的淘汰结果
+#[derive(Debug, Default)]
+pub struct BudgetPlan {
+    pub usage: u64,                               // 当前去重后的本地占用
+    pub freed: u64,                               // 淘汰后释放的本地空间
+    pub evicted: usize,                           // 淘汰的磁盘文件数
+    pub files: HashMap<String, HashSet<String>>,  // 群号 -> 要删除的文件（FileInfo::key）
+}
+
+impl BudgetPlan {
+    pub fn remaining(&self) -> u64 {
+        self.usage.saturating_sub(self.freed)
+    }
+}
+
+/// 被引用的磁盘文件，按最近一次引用的时间淘汰
+struct DiskFile<'a> {
+    refs: Vec<(&'a str, &'a FileInfo)>, // (群号, 引用它的消息)
+    last_used: i64,
+    evictable: bool,
+}
+
+/// 按全局预算从最久未使用的文件开始淘汰，直到本地占用不超过 `budget`
+///
+/// 所有群中仍存在的文件按磁盘文件去重，最近一次被引用的消息时间作为使用时间，
+/// 占用和释放的空间都只计算一次。被置顶群引用的文件、以及有引用不符合 `filter` 的文件不会淘汰；
+/// 淘汰一个文件时，引用它的所有消息都会被选中。`scope` 决定淘汰时实际删除原图、缩略图还是两者。
+pub fn evict_for_budget(
+    stats: &[GroupStats],
+    budget: u64,
+    pinned: &HashSet<String>,
+    filter: &FileFilter,
+    scope: CleanScope,
+) -> BudgetPlan {
+    let mut disk_files: HashMap<(String, &str), DiskFile> = HashMap::new();
+    for stat in stats {
+        let is_pinned = pinned.contains(&stat.group_id);
+        for file in stat.files.iter().filter(|f| f.actual_size.is_some() && !f.file_name.is_empty()) {
+            let entry = disk_files.entry(projection::disk_key(file)).or_insert(DiskFile {
+                refs: Vec::new(),
+                last_used: i64::MIN,
+                evictable: true,
+            });
+            entry.refs.push((stat.group_id.as_str(), file));
+            entry.last_used = entry.last_used.max(file.msg_time);
+            entry.evictable &= !is_pinned && filter.matches(file);
+        }
+    }
+
+    let mut plan = BudgetPlan::default();
+    let mut candidates = Vec::new();
+    for disk_file in disk_files.values() {
+        let (ori, thumb) = projection::local_sizes(disk_file.refs[0].1);
+        plan.usage += ori + thumb;
+
+        let freed = match scope {
+            CleanScope::Both => ori + thumb,
+            CleanScope::OriOnly => ori,
+            CleanScope::ThumbOnly => thumb,
+        };
+        if disk_file.evictable && freed > 0 {
+            candidates.push((disk_file, freed));
+        }
+    }
+    candidates.sort_by_key(|(disk_file, _)| disk_file.last_used);
+
+    for (disk_file, freed) in candidates {
+        if plan.remaining() <= budget {
+            break;
+        }
+        plan.freed += freed;
+        plan.evicted += 1;
+        for &(group_id, file) in &disk_file.refs {
+            plan.files
+                .entry(group_id.to_string())
+                .or_default()
+                .insert(file.key());
+        }
+    }
+
+    plan
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::file_filter::FileRules;
+    use crate::time_range::TimeRange;
+
+    const DAY: i64 = 86400;
+    const BASE: i64 = 1_700_000_000;
+
+    fn keys(names: &[&str]) -> HashSet<String> {
+        names.iter().map(|s| s.to_string()).collect()
+    }
+
+    /// 每天一个文件，f1 最旧
+    fn group(sizes: &[u64]) -> GroupStats {
+        let files = sizes
+            .iter()
+            .enumerate()
+            .map(|(i, &size)| FileInfo::test(i as i64 + 1, &format!("f{}.jpg", i + 1), BASE + i as i64 * DAY, size))
+            .collect();
+        GroupStats::test("1", files)
+    }
+
+    fn all() -> FileFilter {
+        FileFilter::new(TimeRange::All)
+    }
+
+    #[test]
+    fn no_policy_selects_nothing_extra() {
+        assert_eq!(GroupPolicy::None.select(&group(&[1, 2]), &all()), None);
+    }
+
+    #[test]
+    fn keep_newest_deletes_older_files() {
+        let selected = GroupPolicy::KeepNewest(2).select(&group(&[1, 1, 1, 1]), &all());
+        assert_eq!(selected, Some(keys(&["1:f1.jpg", "2:f2.jpg"])));
+
+        let selected = GroupPolicy::KeepNewest(10).select(&group(&[1, 1]), &all());
+        assert_eq!(selected, Some(HashSet::new()));
+    }
+
+    #[test]
+    fn quota_keeps_newest_files_within_the_limit() {
+        // 从最新开始：40 + 30 = 70 ≤ 80，再加 20 超出
+        let selected = GroupPolicy::Quota(80).select(&group(&[10, 20, 30, 40]), &all());
+        assert_eq!(selected, Some(keys(&["1:f1.jpg", "2:f2.jpg"])));
+    }
+
+    #[test]
+    fn missing_files_do_not_use_the_allowance() {
+        let mut stat = group(&[1, 1, 1]);
+        stat.files[2].actual_size = None;
+        let selected = GroupPolicy::KeepNewest(1).select(&stat, &all());
+        assert_eq!(selected, Some(keys(&["1:f1.jpg"])));
+    }
+
+    #[test]
+    fn excess_files_must_also_match_the_filter() {
+        let filter = all().with_rules(FileRules {
+            min_size: 5,
+            ..FileRules::default()
+        });
+        let selected = GroupPolicy::KeepNewest(1).select(&group(&[10, 1, 10, 10]), &filter);
+        assert_eq!(selected, Some(keys(&["1:f1.jpg", "3:f3.jpg"])));
+    }
+
+    /// 群 1 引用 f1、f2，群 2 在之后再次引用 f1（同一个月，同一个磁盘文件）
+    fn shared_groups() -> Vec<GroupStats> {
+        let first = group(&[10, 10]);
+        let second = GroupStats::test("2", vec![FileInfo::test(9, "f1.jpg", BASE + 2 * DAY, 10)]);
+        vec![first, second]
+    }
+
+    #[test]
+    fn budget_evicts_least_recently_used_first() {
+        let stats = [group(&[10, 10, 10, 10])];
+        let plan = evict_for_budget(&stats, 25, &HashSet::new(), &all(), CleanScope::Both);
+        assert_eq!((plan.usage, plan.freed, plan.evicted), (40, 20, 2));
+        assert_eq!(plan.remaining(), 20);
+        assert_eq!(plan.files["1"], keys(&["1:f1.jpg", "2:f2.jpg"]));
+    }
+
+    #[test]
+    fn budget_already_met_evicts_nothing() {
+        let stats = [group(&[10, 10])];
+        let plan = evict_for_budget(&stats, 20, &HashSet::new(), &all(), CleanScope::Both);
+        assert_eq!((plan.usage, plan.freed, plan.evicted), (20, 0, 0));
+        assert!(plan.files.is_empty());
+    }
+
+    #[test]
+    fn shared_files_count_once_and_use_the_last_reference() {
+        let stats = shared_groups();
+        // f1 最后一次被引用比 f2 晚，先淘汰 f2
+        let plan = evict_for_budget(&stats, 10, &HashSet::new(), &all(), CleanScope::Both);
+        assert_eq!((plan.usage, plan.freed, plan.evicted), (20, 10, 1));
+        assert_eq!(plan.files["1"], keys(&["2:f2.jpg"]));
+
+        // 淘汰 f1 时两个群中的引用都被选中
+        let plan = evict_for_budget(&stats, 0, &HashSet::new(), &all(), CleanScope::Both);
+        assert_eq!((plan.freed, plan.evicted), (20, 2));
+        assert_eq!(plan.files["1"], keys(&["1:f1.jpg", "2:f2.jpg"]));
+        assert_eq!(plan.files["2"], keys(&["9:f1.jpg"]));
+    }
+
+    #[test]
+    fn pinned_groups_protect_shared_files() {
+        let plan = evict_for_budget(&shared_groups(), 0, &keys(&["2"]), &all(), CleanScope::Both);
+        assert_eq!((plan.freed, plan.evicted), (10, 1));
+        assert_eq!(plan.remaining(), 10);
+        assert_eq!(plan.files["1"], keys(&["2:f2.jpg"]));
+        assert!(!plan.files.contains_key("2"));
+    }
+
+    #[test]
+    fn files_outside_the_filter_are_kept() {
+        let filter = all().with_rules(FileRules {
+            min_size: 5,
+            ..FileRules::default()
+        });
+        let plan = evict_for_budget(&[group(&[1, 10, 1])], 0, &HashSet::new(), &filter, CleanScope::Both);
+        assert_eq!((plan.usage, plan.freed, plan.evicted), (12, 10, 1));
+        assert_eq!(plan.files["1"], keys(&["2:f2.jpg"]));
+    }
+
+    #[test]
+    fn freed_space_follows_the_scope() {
+        let files = vec![
+            FileInfo::test(1, "a.jpg", BASE, 100).with_thumb(5),
+            FileInfo::test(2, "b.jpg", BASE + DAY, 100),
+        ];
+        let stats = [GroupStats::test("1", files)];
+
+        let plan = evict_for_budget(&stats, 0, &HashSet::new(), &all(), CleanScope::OriOnly);
+        assert_eq!((plan.usage, plan.freed, plan.evicted), (205, 200, 2));
+
+        // 没有缩略图的文件只删缩略图时释放不了空间，不算淘汰
+        let plan = evict_for_budget(&stats, 0, &HashSet::new(), &all(), CleanScope::ThumbOnly);
+        assert_eq!((plan.freed, plan.evicted), (5, 1));
+        assert_eq!(plan.files["1"], keys(&["1:a.jpg"]));
+    }
+
+    #[test]
+    fn presets_cycle_back_to_none() {
+        let mut policy = GroupPolicy::None;
+        for _ in 0..4 {
+            policy = policy.next_preset();
+            assert_ne!(policy, GroupPolicy::None);
+        }
+        assert_eq!(policy.next_preset(), GroupPolicy::None);
+        assert_eq!(GroupPolicy::KeepNewest(7).next_preset(), GroupPolicy::None);
+    }
+}
@@ -0,0 +1,267 @@
+use std::fs;
+use std::path::{Path, PathBuf};
+
+use crate::models::format_bytes;
+
+/// 执行破坏性操作前需要检查的内容
+pub struct PreflightRequest<'a> {
+    pub qq_data_dir: &'a Path,
+    pub writes_source: bool,          // 是否会删除或替换 QQ 目录中的文件
+    pub target_dir: Option<&'a Path>, // 迁移 / 恢复使用的目标目录
+    pub required_bytes: u64,          // 需要写入的字节数
+    pub writes_back: bool,            // 恢复：数据写回 QQ 目录，按 QQ 目录所在磁盘检查空间
+}
+
+/// 运行所有预检查，返回阻止操作的问题说明；为空表示可以执行
+pub fn run(request: &PreflightRequest) -> Vec<String> {
+    let mut issues = Vec::new();
+
+    if let Some(process) = find_running_qq() {
+        issues.push(t!("preflight.qq_running", process));
+    }
+
+    if request.writes_source {
+        if let Err(e) = check_writable(request.qq_data_dir) {
+            issues.push(t!(
+                "preflight.not_writable",
+                request.qq_data_dir.display(),
+                e
+            ));
+        }
+    }
+
+    if let Some(target_dir) = request.target_dir {
+        if is_inside(target_dir, request.qq_data_dir) {
+            issues.push(t!("preflight.target_inside", target_dir.display()));
+        }
+    }
+
+    let destination = if request.writes_back {
+        Some(request.qq_data_dir)
+    } else {
+        request.target_dir
+    };
+    if let Some(destination) = destination.filter(|_| request.required_bytes > 0) {
+        if let Some(available) = available_space(destination) {
+            if available < request.required_bytes {
+                issues.push(t!(
+                    "preflight.no_space",
+                    destination.display(),
+                    format_bytes(request.required_bytes),
+                    format_bytes(available)
+                ));
+            }
+        }
+    }
+
+    issues
+}
+
+/// 通过创建并删除一个临时文件确认目录可写
+fn check_writable(dir: &Path) -> std::io::Result<()> {
+    let probe = dir.join(".qqcleaner_preflight");
+    fs::write(&probe, b"")?;
+    fs::remove_file(&probe)
+}
+
+/// 目标路径可能尚未创建，取最近的已存在祖先目录解析真实路径
+fn resolve_existing(path: &Path) -> Option<PathBuf> {
+    path.ancestors().find_map(|p| p.canonicalize().ok())
+}
+
+fn is_inside(target: &Path, source: &Path) -> bool {
+    match (resolve_existing(target), source.canonicalize()) {
+        (Some(target), Ok(source)) => target.starts_with(source),
+        _ => false,
+    }
+}
+
+#[cfg(unix)]
+fn available_space(path: &Path) -> Option<u64> {
+    use std::ffi::CString;
+    use std::os::unix::ffi::OsStrExt;
+
+    let existing = resolve_existing(path)?;
+    let c_path = CString::new(existing.as_os_str().as_bytes()).ok()?;
+    let mut stat: libc::statvfs = unsafe { std::mem::zeroed() };
+
+    // SAFETY: c_path 是以 NUL 结尾的有效路径，stat 指向足够大小的可写内存
+    if unsafe { libc::statvfs(c_path.as_ptr(), &mut stat) } != 0 {
+        return None;
+    }
+
+    Some(stat.f_bavail as u64 * stat.f_frsize as u64)
+}
+
+#[cfg(not(unix))]
+fn available_space(_path: &Path) -> Option<u64> {
+    None
+}
+
+/// 查找正在运行的 QQ 进程，返回进程描述
+#[cfg(target_os = "linux")]
+fn find_running_qq() -> Option<String> {
+    let entries = fs::read_dir("/proc").ok()?;
+
+    for entry in entries.flatten() {
+        let name = entry.file_name();
+        let Some(pid) = name.to_str().filter(|s| s.chars().all(|c| c.is_ascii_digit())) else {
+            continue;
+        };
+
+        if let Ok(comm) = fs::read_to_string(entry.path().join("comm")) {
+            let comm = comm.trim();
+            if comm.eq_ignore_ascii_case("qq") {
+                return Some(format!("{} pid {}", comm, pid));
+            }
+        }
+    }
+
+    None
+}
+
+#[cfg(target_os = "macos")]
+fn find_running_qq() -> Option<String> {
+    let output = std::process::Command::new("pgrep")
+        .args(["-x", "QQ"])
+        .output()
+        .ok()?;
+
+    let pid = String::from_utf8_lossy(&output.stdout)
+        .lines()
+        .next()?
+        .trim()
+        .to_string();
+
+    Some(format!("QQ pid {}", pid))
+}
+
+#[cfg(not(any(target_os = "linux", target_os = "macos")))]
+fn find_running_qq() -> Option<String> {
+    None
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 每个测试独立的临时目录，结束时删除
+    struct TempDir(PathBuf);
+
+    impl TempDir {
+        fn new(name: &str) -> Self {
+            let path = std::env::temp_dir().join(format!("qqcleaner_preflight_{}_{}", name, std::process::id()));
+            let _ = fs::remove_dir_all(&path);
+            fs::create_dir_all(&path).unwrap();
+            TempDir(path)
+        }
+    }
+
+    impl Drop for TempDir {
+        fn drop(&mut self) {
+            let _ = fs::remove_dir_all(&self.0);
+        }
+    }
+
+    #[test]
+    fn target_inside_source_is_detected() {
+        let root = TempDir::new("inside");
+        let source = root.0.join("qq");
+        fs::create_dir_all(source.join("2024-01")).unwrap();
+        fs::create_dir_all(root.0.join("qq_archive")).unwrap();
+
+        assert!(is_inside(&source, &source));
+        assert!(is_inside(&source.join("2024-01"), &source));
+        // 尚未创建的目标按最近的已存在祖先判断
+        assert!(is_inside(&source.join("archive/2024"), &source));
+        assert!(!is_inside(&root.0.join("archive"), &source));
+        // 名称前缀相同的兄弟目录不算在内
+        assert!(!is_inside(&root.0.join("qq_archive"), &source));
+        // 数据目录不存在时无法判断，不阻止操作
+        assert!(!is_inside(&root.0.join("archive"), &root.0.join("missing")));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn symlinked_target_is_resolved() {
+        let root = TempDir::new("symlink");
+        let source = root.0.join("qq");
+        fs::create_dir_all(&source).unwrap();
+        std::os::unix::fs::symlink(&source, root.0.join("link")).unwrap();
+
+        assert!(is_inside(&root.0.join("link/archive"), &source));
+    }
+
+    #[test]
+    fn writable_check_leaves_no_probe_behind() {
+        let root = TempDir::new("writable");
+        check_writable(&root.0).unwrap();
+        assert_eq!(fs::read_dir(&root.0).unwrap().count(), 0);
+        assert!(check_writable(&root.0.join("missing")).is_err());
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn read_only_directory_is_not_writable() {
+        use std::os::unix::fs::PermissionsExt;
+
+        // root 不受目录权限限制
+        if unsafe { libc::geteuid() } == 0 {
+            return;
+        }
+        let root = TempDir::new("readonly");
+        fs::set_permissions(&root.0, fs::Permissions::from_mode(0o555)).unwrap();
+        let result = check_writable(&root.0);
+        fs::set_permissions(&root.0, fs::Permissions::from_mode(0o755)).unwrap();
+        assert!(result.is_err());
+    }
+
+    #[test]
+    fn run_reports_target_inside_source() {
+        let root = TempDir::new("run");
+        let source = root.0.join("qq");
+        fs::create_dir_all(&source).unwrap();
+        let target = source.join("archive");
+
+        let issues = run(&PreflightRequest {
+            qq_data_dir: &source,
+            writes_source: true,
+            target_dir: Some(&target),
+            required_bytes: 0,
+            writes_back: false,
+        });
+        assert!(issues.iter().any(|issue| issue.contains(&target.display().to_string())));
+
+        let outside = root.0.join("archive");
+        let issues = run(&PreflightRequest {
+            qq_data_dir: &source,
+            writes_source: true,
+            target_dir: Some(&outside),
+            required_bytes: 0,
+            writes_back: false,
+        });
+        assert!(!issues.iter().any(|issue| issue.contains(&outside.display().to_string())));
+    }
+
+    #[cfg(unix)]
+    #[test]
+    fn free_space_is_checked_where_files_are_written() {
+        let root = TempDir::new("space");
+        let source = root.0.join("qq");
+        let target = root.0.join("archive");
+        fs::create_dir_all(&source).unwrap();
+
+        let request = |writes_back| PreflightRequest {
+            qq_data_dir: &source,
+            writes_source: false,
+            target_dir: Some(&target),
+            required_bytes: u64::MAX,
+            writes_back,
+        };
+        // 迁移写入目标目录，恢复写回 QQ 数据目录
+        let issues = run(&request(false));
+        assert!(issues.iter().any(|issue| issue.contains(&target.display().to_string())));
+        let issues = run(&request(true));
+        assert!(issues.iter().any(|issue| issue.contains(&source.display().to_string())));
+    }
+}
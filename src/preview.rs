@@ -0,0 +1,379 @@
+use anyhow::{Context, Result};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use crossterm::{cursor::MoveTo, queue};
+use image::{imageops::FilterType, DynamicImage, RgbImage};
+use ratatui::layout::Rect;
+use ratatui::style::Color;
+use std::fmt::Write as _;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use tokio::sync::oneshot::{self, error::TryRecvError};
+
+use crate::file_checker::FileChecker;
+use crate::models::FileInfo;
+
+/// kitty 协议中预览图片使用的固定 id，切换文件时按 id 删除旧图
+const KITTY_IMAGE_ID: u32 = 7301;
+
+/// 终端支持的图片显示方式
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum GraphicsProtocol {
+    Kitty,
+    Iterm2,
+    Sixel,
+    HalfBlock,  // 使用 ▀ 字符和前景/背景色，任何真彩色终端都可用
+}
+
+impl GraphicsProtocol {
+    /// 根据环境变量推断终端能力，无法确定时退回半块字符
+    pub fn detect() -> Self {
+        let term = std::env::var("TERM").unwrap_or_default();
+        let term_program = std::env::var("TERM_PROGRAM").unwrap_or_default();
+
+        if std::env::var_os("KITTY_WINDOW_ID").is_some() || term.contains("kitty") || term_program == "ghostty" {
+            GraphicsProtocol::Kitty
+        } else if term_program == "iTerm.app" || term_program == "WezTerm" {
+            GraphicsProtocol::Iterm2
+        } else if term.contains("sixel") || term.starts_with("foot") || term.starts_with("mlterm") {
+            GraphicsProtocol::Sixel
+        } else {
+            GraphicsProtocol::HalfBlock
+        }
+    }
+
+    pub fn description(&self) -> &'static str {
+        match self {
+            GraphicsProtocol::Kitty => "kitty",
+            GraphicsProtocol::Iterm2 => "iTerm2",
+            GraphicsProtocol::Sixel => "sixel",
+            GraphicsProtocol::HalfBlock => t!("preview.half_block"),
+        }
+    }
+}
+
+/// 预览区域中要显示的内容
+pub enum PreviewContent {
+    HalfBlock(Vec<Vec<(Color, Color)>>),  // 每个字符格的上半、下半像素颜色
+    Escape(String),                       // 直接写入终端的图形协议序列
+    Error(String),
+}
+
+/// 预览对应的文件和区域，任一变化都需要重新生成
+type PreviewKey = (PathBuf, Rect);
+
+pub struct PreviewState {
+    pub protocol: GraphicsProtocol,
+    pub area: Option<Rect>,          // 预览区域（不含边框），由界面布局计算
+    pub source: Option<PathBuf>,     // 当前显示的图片文件
+    pub content: Option<PreviewContent>,
+    key: Option<PreviewKey>,
+    loading: Option<(PreviewKey, oneshot::Receiver<PreviewContent>)>,  // 后台解码中的图片，同一时间只有一个
+    emitted: bool,                   // 图形协议图片已写入终端
+    pending_emit: bool,
+    pending_delete: bool,
+}
+
+impl PreviewState {
+    pub fn new() -> Self {
+        Self {
+            protocol: GraphicsProtocol::detect(),
+            area: None,
+            source: None,
+            content: None,
+            key: None,
+            loading: None,
+            emitted: false,
+            pending_emit: false,
+            pending_delete: false,
+        }
+    }
+
+    /// 图片正在后台解码，还没有可显示的内容
+    pub fn is_loading(&self) -> bool {
+        self.key.is_some() && self.content.is_none()
+    }
+
+    /// 根据要预览的文件和当前区域更新内容
+    ///
+    /// 解码和缩放在阻塞线程池中进行，结果在之后的调用中取回，不会卡住界面。
+    /// 返回 true 表示上一张图片是由 iTerm2 / sixel 直接写入终端的，
+    /// 需要清屏让界面完整重绘以擦除它。
+    pub fn update(&mut self, target: Option<PathBuf>) -> bool {
+        let key = match (target, self.area) {
+            (Some(path), Some(area)) if area.width > 0 && area.height > 0 => Some((path, area)),
+            _ => None,
+        };
+
+        let mut needs_clear = false;
+        if key != self.key {
+            if self.emitted {
+                match self.protocol {
+                    GraphicsProtocol::Kitty => self.pending_delete = true,
+                    _ => needs_clear = true,
+                }
+                self.emitted = false;
+            }
+
+            self.source = key.as_ref().map(|(path, _)| path.clone());
+            self.key = key;
+            self.content = None;
+            self.pending_emit = false;
+        }
+
+        self.receive();
+        if self.loading.is_none() && self.content.is_none() {
+            if let Some(key) = self.key.clone() {
+                self.start(key);
+            }
+        }
+
+        needs_clear
+    }
+
+    /// 取回后台解码的结果；对应的文件已不是当前预览时丢弃
+    fn receive(&mut self) {
+        let Some((key, receiver)) = self.loading.as_mut() else {
+            return;
+        };
+        let content = match receiver.try_recv() {
+            Ok(content) => content,
+            Err(TryRecvError::Empty) => return,
+            // 解码线程异常退出
+            Err(TryRecvError::Closed) => PreviewContent::Error(t!("preview.decode_failed", key.0.display())),
+        };
+
+        let current = self.key.as_ref() == Some(&*key);
+        self.loading = None;
+        if current {
+            self.pending_emit = matches!(content, PreviewContent::Escape(_));
+            self.content = Some(content);
+        }
+    }
+
+    fn start(&mut self, key: PreviewKey) {
+        let (sender, receiver) = oneshot::channel();
+        let (path, area) = key.clone();
+        let protocol = self.protocol;
+        tokio::task::spawn_blocking(move || {
+            let content = render(&path, area, protocol).unwrap_or_else(|e| PreviewContent::Error(format!("{:#}", e)));
+            let _ = sender.send(content);
+        });
+        self.loading = Some((key, receiver));
+    }
+
+    /// 界面绘制完成后，把图形协议的图片写到预览区域
+    pub fn emit<W: Write>(&mut self, out: &mut W) -> Result<()> {
+        if self.pending_delete {
+            write!(out, "\x1b_Ga=d,d=I,i={},q=2\x1b\\", KITTY_IMAGE_ID)?;
+            self.pending_delete = false;
+        }
+
+        if self.pending_emit {
+            if let (Some(PreviewContent::Escape(seq)), Some(area)) = (&self.content, self.area) {
+                queue!(out, MoveTo(area.x, area.y))?;
+                out.write_all(seq.as_bytes())?;
+                self.emitted = true;
+            }
+            self.pending_emit = false;
+        }
+
+        out.flush()?;
+        Ok(())
+    }
+}
+
+/// 选择用于预览的文件：优先使用 `_0` / `_720` 缩略图，没有时才读取原图
+pub fn preview_path(qq_data_dir: &Path, file: &FileInfo) -> Option<PathBuf> {
+    if file.file_name.is_empty() {
+        return None;
+    }
+
+    let base_dir = qq_data_dir.join(file.month_dir());
+
+    FileChecker::get_thumb_filenames(&file.file_name)
+        .into_iter()
+        .map(|name| base_dir.join("Thumb").join(name))
+        .chain(std::iter::once(base_dir.join("Ori").join(&file.file_name)))
+        .find(|path| path.exists())
+}
+
+fn render(path: &Path, area: Rect, protocol: GraphicsProtocol) -> Result<PreviewContent> {
+    match protocol {
+        GraphicsProtocol::Iterm2 => {
+            // iTerm2 协议接受完整的图片文件，先缩小到预览区域再编码为 PNG，避免把整张原图写入终端
+            let (cell_w, cell_h) = cell_pixel_size();
+            let img = load_image(path)?
+                .resize(area.width as u32 * cell_w, area.height as u32 * cell_h, FilterType::Triangle);
+            let mut bytes = Vec::new();
+            img.write_to(&mut std::io::Cursor::new(&mut bytes), image::ImageFormat::Png)
+                .with_context(|| t!("preview.encode_failed", path.display()))?;
+            Ok(PreviewContent::Escape(format!(
+                "\x1b]1337;File=inline=1;size={};width={};height={};preserveAspectRatio=1:{}\x07",
+                bytes.len(),
+                area.width,
+                area.height,
+                STANDARD.encode(&bytes)
+            )))
+        }
+        GraphicsProtocol::Kitty => {
+            let (cell_w, cell_h) = cell_pixel_size();
+            let img = load_image(path)?
+                .resize(area.width as u32 * cell_w, area.height as u32 * cell_h, FilterType::Triangle)
+                .to_rgba8();
+            Ok(PreviewContent::Escape(encode_kitty(
+                img.width(),
+                img.height(),
+                img.as_raw(),
+            )))
+        }
+        GraphicsProtocol::Sixel => {
+            let (cell_w, cell_h) = cell_pixel_size();
+            let img = load_image(path)?
+                .resize(area.width as u32 * cell_w, area.height as u32 * cell_h, FilterType::Triangle)
+                .to_rgb8();
+            Ok(PreviewContent::Escape(encode_sixel(&img)))
+        }
+        GraphicsProtocol::HalfBlock => {
+            // 每个字符格显示上下两个像素
+            let img = load_image(path)?
+                .resize(area.width as u32, area.height as u32 * 2, FilterType::Triangle)
+                .to_rgb8();
+
+            let pixel = |x: u32, y: u32| {
+                if y < img.height() {
+                    let p = img.get_pixel(x, y);
+                    Color::Rgb(p[0], p[1], p[2])
+                } else {
+                    Color::Reset
+                }
+            };
+
+            let rows = (0..img.height())
+                .step_by(2)
+                .map(|y| (0..img.width()).map(|x| (pixel(x, y), pixel(x, y + 1))).collect())
+                .collect();
+
+            Ok(PreviewContent::HalfBlock(rows))
+        }
+    }
+}
+
+fn load_image(path: &Path) -> Result<DynamicImage> {
+    image::ImageReader::open(path)
+        .with_context(|| t!("preview.read_failed", path.display()))?
+        .with_guessed_format()?
+        .decode()
+        .with_context(|| t!("preview.decode_failed", path.display()))
+}
+
+/// 单个字符格的像素大小，终端不报告时按常见的 8x16 估算
+fn cell_pixel_size() -> (u32, u32) {
+    match crossterm::terminal::window_size() {
+        Ok(size) if size.columns > 0 && size.rows > 0 && size.width > 0 && size.height > 0 => (
+            (size.width / size.columns).max(1) as u32,
+            (size.height / size.rows).max(1) as u32,
+        ),
+        _ => (8, 16),
+    }
+}
+
+/// kitty 图形协议：RGBA 原始像素，按 4096 字节分块传输
+fn encode_kitty(width: u32, height: u32, rgba: &[u8]) -> String {
+    let payload = STANDARD.encode(rgba);
+    let chunks: Vec<&str> = payload
+        .as_bytes()
+        .chunks(4096)
+        .map(|c| std::str::from_utf8(c).unwrap_or_default())
+        .collect();
+
+    let mut out = String::new();
+    for (idx, chunk) in chunks.iter().enumerate() {
+        let more = if idx + 1 < chunks.len() { 1 } else { 0 };
+        if idx == 0 {
+            let _ = write!(
+                out,
+                "\x1b_Ga=T,f=32,s={},v={},i={},q=2,C=1,m={};{}\x1b\\",
+                width, height, KITTY_IMAGE_ID, more, chunk
+            );
+        } else {
+            let _ = write!(out, "\x1b_Gm={};{}\x1b\\", more, chunk);
+        }
+    }
+    out
+}
+
+/// sixel 编码：颜色量化到 6x6x6 色立方，每 6 行像素为一个条带
+fn encode_sixel(img: &RgbImage) -> String {
+    let (width, height) = img.dimensions();
+    let level = |v: u8| (v as u16 * 5 + 127) / 255;
+    let indices: Vec<u16> = img
+        .pixels()
+        .map(|p| level(p[0]) * 36 + level(p[1]) * 6 + level(p[2]))
+        .collect();
+
+    let mut out = String::from("\x1bPq");
+    let _ = write!(out, "\"1;1;{};{}", width, height);
+
+    let mut used = [false; 216];
+    for &idx in &indices {
+        used[idx as usize] = true;
+    }
+    for (idx, _) in used.iter().enumerate().filter(|(_, &u)| u) {
+        let (r, g, b) = (idx / 36, (idx / 6) % 6, idx % 6);
+        let _ = write!(out, "#{};2;{};{};{}", idx, r * 20, g * 20, b * 20);
+    }
+
+    for band_top in (0..height).step_by(6) {
+        let band_height = (height - band_top).min(6);
+
+        let mut band_colors = [false; 216];
+        for y in band_top..band_top + band_height {
+            for x in 0..width {
+                band_colors[indices[(y * width + x) as usize] as usize] = true;
+            }
+        }
+
+        let mut first = true;
+        for (color, _) in band_colors.iter().enumerate().filter(|(_, &u)| u) {
+            if !first {
+                out.push('$');
+            }
+            first = false;
+            let _ = write!(out, "#{}", color);
+
+            let mut run_char = '?';
+            let mut run_len = 0u32;
+            for x in 0..width {
+                let mut bits = 0u8;
+                for dy in 0..band_height {
+                    if indices[((band_top + dy) * width + x) as usize] as usize == color {
+                        bits |= 1 << dy;
+                    }
+                }
+                let ch = (63 + bits) as char;
+                if ch == run_char {
+                    run_len += 1;
+                } else {
+                    push_sixel_run(&mut out, run_char, run_len);
+                    run_char = ch;
+                    run_len = 1;
+                }
+            }
+            push_sixel_run(&mut out, run_char, run_len);
+        }
+        out.push('-');
+    }
+
+    out.push_str("\x1b\\");
+    out
+}
+
+fn push_sixel_run(out: &mut String, ch: char, len: u32) {
+    match len {
+        0 => {}
+        1..=3 => (0..len).for_each(|_| out.push(ch)),
+        _ => {
+            let _ = write!(out, "!{}{}", len, ch);
+        }
+    }
+}
@@ -0,0 +1,206 @@
+use std::collections::HashMap;
+
+use crate::file_filter::FileFilter;
+use crate::models::{CleanScope, FileInfo, GroupStats};
+
+/// 清理后实际能释放的磁盘空间预测
+///
+/// 同一个磁盘文件（月份目录 + 文件名）可能被多个群或多条消息引用，只计算一次。
+/// 符号链接只删除链接本身，不释放本地空间，单独统计。
+#[derive(Debug, Clone, Copy, Default)]
+pub struct Projection {
+    pub ori_local: u64,     // 会被删除的原图本地大小
+    pub thumb_local: u64,   // 会被删除的缩略图本地大小
+    pub ori_linked: u64,    // 原图中只删除符号链接的部分
+    pub thumb_linked: u64,  // 缩略图中只删除符号链接的部分
+    pub ori_shared: u64,    // ori_local 中同时被范围外消息引用的部分
+    pub thumb_shared: u64,  // thumb_local 中同时被范围外消息引用的部分
+    pub ori_dedup: u64,     // 原图重复引用按记录累加时多算的大小
+    pub thumb_dedup: u64,   // 缩略图重复引用按记录累加时多算的大小
+    pub files: usize,       // 去重后涉及的磁盘文件组数
+}
+
+impl Projection {
+    /// 按清理范围实际释放的本地空间
+    pub fn freed(&self, scope: CleanScope) -> u64 {
+        self.pick(scope, self.ori_local, self.thumb_local)
+    }
+
+    pub fn linked(&self, scope: CleanScope) -> u64 {
+        self.pick(scope, self.ori_linked, self.thumb_linked)
+    }
+
+    pub fn shared(&self, scope: CleanScope) -> u64 {
+        self.pick(scope, self.ori_shared, self.thumb_shared)
+    }
+
+    pub fn dedup(&self, scope: CleanScope) -> u64 {
+        self.pick(scope, self.ori_dedup, self.thumb_dedup)
+    }
+
+    fn pick(&self, scope: CleanScope, ori: u64, thumb: u64) -> u64 {
+        let ori = if scope.includes_ori() { ori } else { 0 };
+        let thumb = if scope.includes_thumb() { thumb } else { 0 };
+        ori + thumb
+    }
+}
+
+/// 被引用的磁盘文件及引用情况
+struct DiskFile<'a> {
+    file: &'a FileInfo,
+    selected_refs: u64, // 参与清理的引用数
+    other_refs: usize,  // 不参与清理的引用数
+}
+
+/// 计算清理后能释放的空间
+///
+/// `filter_for` 返回群组参与清理时使用的文件筛选器，不参与清理的群返回 `None`。
+/// 其余群中引用同一文件的消息也会被统计为共享引用。
+pub fn project<F>(stats: &[GroupStats], filter_for: F) -> Projection
+where
+    F: Fn(&GroupStats) -> Option<FileFilter>,
+{
+    let mut disk_files: HashMap<(String, &str), DiskFile> = HashMap::new();
+
+    for stat in stats {
+        let filter = filter_for(stat);
+        for file in &stat.files {
+            if file.file_name.is_empty() || file.actual_size.is_none() {
+                continue;
+            }
+
+            let selected = filter.as_ref().is_some_and(|filter| filter.matches(file));
+            let entry = disk_files
+                .entry(disk_key(file))
+                .or_insert(DiskFile {
+                    file,
+                    selected_refs: 0,
+                    other_refs: 0,
+                });
+            if selected {
+                entry.selected_refs += 1;
+            } else {
+                entry.other_refs += 1;
+            }
+        }
+    }
+
+    let mut projection = Projection::default();
+    for disk_file in disk_files.values().filter(|d| d.selected_refs > 0) {
+        let file = disk_file.file;
+        let ori_linked = file.ori_linked_size;
+        let thumb_linked = file.linked_size.saturating_sub(ori_linked);
+        let (ori_local, thumb_local) = local_sizes(file);
+
+        projection.files += 1;
+        projection.ori_local += ori_local;
+        projection.thumb_local += thumb_local;
+        projection.ori_linked += ori_linked;
+        projection.thumb_linked += thumb_linked;
+        projection.ori_dedup += ori_local * (disk_file.selected_refs - 1);
+        projection.thumb_dedup += thumb_local * (disk_file.selected_refs - 1);
+        if disk_file.other_refs > 0 {
+            projection.ori_shared += ori_local;
+            projection.thumb_shared += thumb_local;
+        }
+    }
+
+    projection
+}
+
+/// 区分磁盘文件的键：月份目录和文件名，多条消息可能引用同一个文件
+pub fn disk_key(file: &FileInfo) -> (String, &str) {
+    (file.month_dir(), file.file_name.as_str())
+}
+
+/// 文件原图和缩略图占用的本地空间，不含符号链接指向的部分
+pub fn local_sizes(file: &FileInfo) -> (u64, u64) {
+    let ori_linked = file.ori_linked_size;
+    let thumb_linked = file.linked_size.saturating_sub(ori_linked);
+    (
+        file.ori_size.unwrap_or(0).saturating_sub(ori_linked),
+        file.thumb_size.unwrap_or(0).saturating_sub(thumb_linked),
+    )
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::time_range::TimeRange;
+
+    const NOV_2023: i64 = 1_700_000_000;
+    const DEC_2023: i64 = 1_702_000_000;
+
+    fn all() -> Option<FileFilter> {
+        Some(FileFilter::new(TimeRange::All))
+    }
+
+    #[test]
+    fn shared_disk_files_are_counted_once() {
+        let stats = vec![
+            GroupStats::test("1", vec![FileInfo::test(1, "a.jpg", NOV_2023, 100).with_thumb(10)]),
+            GroupStats::test("2", vec![FileInfo::test(7, "a.jpg", NOV_2023, 100).with_thumb(10)]),
+        ];
+
+        let projection = project(&stats, |_| all());
+        assert_eq!(projection.files, 1);
+        assert_eq!(projection.freed(CleanScope::Both), 110);
+        assert_eq!(projection.dedup(CleanScope::Both), 110);
+        assert_eq!(projection.shared(CleanScope::Both), 0);
+    }
+
+    #[test]
+    fn same_name_in_other_month_is_a_different_file() {
+        let stats = vec![GroupStats::test(
+            "1",
+            vec![FileInfo::test(1, "a.jpg", NOV_2023, 100), FileInfo::test(2, "a.jpg", DEC_2023, 50)],
+        )];
+
+        let projection = project(&stats, |_| all());
+        assert_eq!(projection.files, 2);
+        assert_eq!(projection.freed(CleanScope::Both), 150);
+    }
+
+    #[test]
+    fn references_outside_the_cleanup_are_reported_as_shared() {
+        let stats = vec![
+            GroupStats::test("1", vec![FileInfo::test(1, "a.jpg", NOV_2023, 100)]),
+            GroupStats::test("2", vec![FileInfo::test(7, "a.jpg", NOV_2023, 100)]),
+        ];
+
+        let projection = project(&stats, |stat| if stat.group_id == "1" { all() } else { None });
+        assert_eq!(projection.freed(CleanScope::Both), 100);
+        assert_eq!(projection.shared(CleanScope::Both), 100);
+    }
+
+    #[test]
+    fn scope_and_symlinks_are_split() {
+        let mut file = FileInfo::test(1, "a.jpg", NOV_2023, 100).with_thumb(20);
+        // 原图已经迁移为符号链接
+        file.ori_linked_size = 100;
+        file.linked_size = 100;
+        let stats = vec![GroupStats::test("1", vec![file])];
+
+        let projection = project(&stats, |_| all());
+        assert_eq!(projection.freed(CleanScope::Both), 20);
+        assert_eq!(projection.freed(CleanScope::OriOnly), 0);
+        assert_eq!(projection.freed(CleanScope::ThumbOnly), 20);
+        assert_eq!(projection.linked(CleanScope::OriOnly), 100);
+        assert_eq!(projection.linked(CleanScope::ThumbOnly), 0);
+    }
+
+    #[test]
+    fn missing_and_unmatched_files_are_ignored() {
+        let mut missing = FileInfo::test(2, "gone.jpg", NOV_2023, 100);
+        missing.actual_size = None;
+        let stats = vec![GroupStats::test(
+            "1",
+            vec![FileInfo::test(1, "a.jpg", NOV_2023, 100), missing, FileInfo::test(3, "new.jpg", DEC_2023, 30)],
+        )];
+
+        let filter = FileFilter::new(TimeRange::Before(chrono::NaiveDate::from_ymd_opt(2023, 12, 1).unwrap()));
+        let projection = project(&stats, |_| Some(filter.clone()));
+        assert_eq!(projection.files, 1);
+        assert_eq!(projection.freed(CleanScope::Both), 100);
+    }
+}
@@ -0,0 +1,111 @@
+/// 模糊匹配结果
+#[derive(Debug, Clone)]
+pub struct FuzzyMatch {
+    pub score: i64,
+    pub positions: Vec<usize>,  // 匹配到的字符下标（按字符计，不是字节）
+}
+
+/// 忽略大小写的子序列匹配：查询中的字符按顺序出现在文本中即视为匹配
+///
+/// 连续匹配和靠前的匹配得分更高，完整子串匹配得分最高。
+pub fn fuzzy_match(query: &str, text: &str) -> Option<FuzzyMatch> {
+    let query: Vec<char> = query.chars().map(fold_case).collect();
+    if query.is_empty() {
+        return Some(FuzzyMatch {
+            score: 0,
+            positions: Vec::new(),
+        });
+    }
+
+    let text: Vec<char> = text.chars().collect();
+    let lower: Vec<char> = text.iter().copied().map(fold_case).collect();
+
+    // 完整子串优先
+    if let Some(start) = lower
+        .windows(query.len())
+        .position(|window| window == query.as_slice())
+    {
+        return Some(FuzzyMatch {
+            score: 1000 - start as i64,
+            positions: (start..start + query.len()).collect(),
+        });
+    }
+
+    let mut positions = Vec::with_capacity(query.len());
+    let mut score = 0i64;
+    let mut next = 0usize;
+
+    for &qc in &query {
+        let found = (next..lower.len()).find(|&i| lower[i] == qc)?;
+        if positions.last().map(|&last| last + 1 == found).unwrap_or(false) {
+            score += 10;
+        }
+        score -= (found - next) as i64;
+        positions.push(found);
+        next = found + 1;
+    }
+
+    Some(FuzzyMatch { score, positions })
+}
+
+/// 查询和文本使用同一种折叠方式，并保持一个字符对应一个字符，`positions` 才能对上原文
+///
+/// 小写形式有多个字符时（如 `İ` -> `i̇`）只取第一个。
+fn fold_case(c: char) -> char {
+    c.to_lowercase().next().unwrap_or(c)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn empty_query_matches_everything() {
+        let matched = fuzzy_match("", "摄影交流群").unwrap();
+        assert_eq!(matched.score, 0);
+        assert!(matched.positions.is_empty());
+    }
+
+    #[test]
+    fn matching_ignores_case() {
+        assert!(fuzzy_match("QQ", "my qq group").is_some());
+        assert!(fuzzy_match("abc", "ABC").is_some());
+        assert!(fuzzy_match("abd", "abc").is_none());
+        // 子序列必须按顺序出现
+        assert!(fuzzy_match("ba", "ab").is_none());
+    }
+
+    #[test]
+    fn substring_matches_rank_first() {
+        let substring = fuzzy_match("abc", "xxxxxxxxabc").unwrap();
+        let scattered = fuzzy_match("abc", "abxc").unwrap();
+        assert!(substring.score > scattered.score);
+
+        // 靠前的子串得分更高
+        let early = fuzzy_match("abc", "abc___").unwrap();
+        let late = fuzzy_match("abc", "___abc").unwrap();
+        assert!(early.score > late.score);
+    }
+
+    #[test]
+    fn contiguous_characters_earn_a_bonus() {
+        let contiguous = fuzzy_match("abd", "abxxd").unwrap();
+        let split = fuzzy_match("abd", "axbxd").unwrap();
+        assert!(contiguous.score > split.score);
+    }
+
+    #[test]
+    fn multi_char_lowercase_matches_itself() {
+        let matched = fuzzy_match("İstanbul", "İstanbul 同乡会").unwrap();
+        assert_eq!(matched.positions, (0..8).collect::<Vec<_>>());
+        assert!(fuzzy_match("İ", "xİy").is_some());
+        assert_eq!(fuzzy_match("i", "İ").unwrap().positions, vec![0]);
+    }
+
+    #[test]
+    fn positions_are_char_indices() {
+        assert_eq!(fuzzy_match("交流", "摄影交流群").unwrap().positions, vec![2, 3]);
+        assert_eq!(fuzzy_match("摄群", "摄影交流群").unwrap().positions, vec![0, 4]);
+        assert_eq!(fuzzy_match("ac", "ABC").unwrap().positions, vec![0, 2]);
+    }
+}
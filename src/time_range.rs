@@ -1,28 +1,252 @@
-use chrono::Utc;
+use anyhow::{bail, Result};
+use chrono::{NaiveDate, Utc};
 
-#[derive(Debug, Clone, Copy)]
+/// 参与清理的文件时间范围
+///
+/// 按 UTC 计算，与 Pic 目录的月份划分和时间分布图保持一致。
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
 pub enum TimeRange {
     All,
-    DaysAgo(i64),
+    OlderThan(i64),                // N 天之前的文件
+    NewerThan(i64),                // 最近 N 天内的文件
+    Before(NaiveDate),             // 该日期之前（不含当天）
+    After(NaiveDate),              // 该日期之后（不含当天）
+    Between(NaiveDate, NaiveDate), // 闭区间，包含首尾两天
 }
 
 impl TimeRange {
-    pub fn should_delete(&self, timestamp: i64) -> bool {
+    /// 按 `t` 键循环的预设范围，自定义范围的下一个是全部
+    pub fn next_preset(&self) -> TimeRange {
         match self {
+            TimeRange::All => TimeRange::OlderThan(7),
+            TimeRange::OlderThan(7) => TimeRange::OlderThan(30),
+            TimeRange::OlderThan(30) => TimeRange::OlderThan(90),
+            TimeRange::OlderThan(90) => TimeRange::OlderThan(180),
+            _ => TimeRange::All,
+        }
+    }
+
+    pub fn should_delete(&self, timestamp: i64) -> bool {
+        let now = Utc::now().timestamp();
+        match *self {
             TimeRange::All => true,
-            TimeRange::DaysAgo(days) => {
-                let now = Utc::now().timestamp();
-                let cutoff = now - (days * 86400);
-                timestamp < cutoff
-            }
+            TimeRange::OlderThan(days) => timestamp < days_ago(now, days),
+            TimeRange::NewerThan(days) => timestamp >= days_ago(now, days),
+            TimeRange::Before(date) => timestamp < day_start(date),
+            TimeRange::After(date) => timestamp >= day_end(date),
+            TimeRange::Between(start, end) => timestamp >= day_start(start) && timestamp < day_end(end),
         }
     }
 
     pub fn description(&self) -> String {
         match self {
-            TimeRange::All => "全部时间".to_string(),
-            TimeRange::DaysAgo(days) => format!("{} 天前", days),
+            TimeRange::All => t!("time_range.all").to_string(),
+            TimeRange::OlderThan(days) => t!("time_range.older_than", days),
+            TimeRange::NewerThan(days) => t!("time_range.newer_than", days),
+            TimeRange::Before(date) => t!("time_range.before", date),
+            TimeRange::After(date) => t!("time_range.after", date),
+            TimeRange::Between(start, end) => t!("time_range.between", start, end),
+        }
+    }
+
+    /// 解析手动输入的时间范围
+    ///
+    /// 支持的写法：
+    /// - `all`：全部
+    /// - `30d`、`>30d`、`older 30`：30 天之前
+    /// - `<7d`、`newer 7`：最近 7 天
+    /// - `<2023-01-01`、`before 2023-01-01`：该日期之前
+    /// - `>2023-01-01`、`after 2023-01-01`：该日期之后
+    /// - `2022-03..2022-06`、`2022-03~2022-06`、`between 2022-03 and 2022-06`：闭区间
+    /// - `2022-03`、`2022`：整月或整年
+    ///
+    /// 日期可以写成年、年-月或年-月-日，作为区间起点时取第一天，作为终点时取最后一天。
+    pub fn parse(input: &str) -> Result<TimeRange> {
+        let input = input.trim().to_lowercase();
+
+        if input.is_empty() || input == "all" || input == "全部" {
+            return Ok(TimeRange::All);
+        }
+
+        // 单独的数字会和年份混淆，`>`、`<` 和不带关键字时必须带单位
+        let has_unit = input.ends_with('d') || input.ends_with('天');
+        let older = strip_keyword(&input, &["older"])
+            .or_else(|| strip_keyword(&input, &[">"]).filter(|_| has_unit));
+        if let Some(days) = older.and_then(parse_days) {
+            return Ok(TimeRange::OlderThan(days));
+        }
+        let newer = strip_keyword(&input, &["newer"])
+            .or_else(|| strip_keyword(&input, &["<"]).filter(|_| has_unit));
+        if let Some(days) = newer.and_then(parse_days) {
+            return Ok(TimeRange::NewerThan(days));
+        }
+        if has_unit {
+            if let Some(days) = parse_days(&input) {
+                return Ok(TimeRange::OlderThan(days));
+            }
+        }
+
+        if let Some(rest) = strip_keyword(&input, &["before", "<"]) {
+            let (start, _) = parse_period(rest)?;
+            return Ok(TimeRange::Before(start));
+        }
+        if let Some(rest) = strip_keyword(&input, &["after", ">"]) {
+            let (_, end) = parse_period(rest)?;
+            return Ok(TimeRange::After(end));
+        }
+
+        let body = strip_keyword(&input, &["between"]).unwrap_or(&input);
+        let bounds = [" and ", "..", "~", " to "]
+            .iter()
+            .find_map(|sep| body.split_once(sep));
+        let (start, end) = match bounds {
+            Some((from, to)) => (parse_period(from)?.0, parse_period(to)?.1),
+            None => parse_period(body)?,
+        };
+
+        if start > end {
+            bail!(t!("time_range.error_order", start, end));
         }
+        Ok(TimeRange::Between(start, end))
     }
 }
 
+fn strip_keyword<'a>(input: &'a str, keywords: &[&str]) -> Option<&'a str> {
+    keywords
+        .iter()
+        .find_map(|keyword| input.strip_prefix(keyword))
+        .map(str::trim)
+}
+
+/// "30"、"30d"、"30天" -> 30
+fn parse_days(input: &str) -> Option<i64> {
+    let digits = input
+        .strip_suffix('d')
+        .or_else(|| input.strip_suffix('天'))
+        .unwrap_or(input)
+        .trim();
+    digits.parse().ok().filter(|days| *days >= 0)
+}
+
+/// 把年、年-月、年-月-日解析为它覆盖的第一天和最后一天
+fn parse_period(input: &str) -> Result<(NaiveDate, NaiveDate)> {
+    let input = input.trim();
+    let parts: Vec<&str> = input.split(['-', '/', '.']).collect();
+    let numbers: Option<Vec<u32>> = parts.iter().map(|p| p.trim().parse().ok()).collect();
+    // 年份必须写全，避免把 "30" 当成公元 30 年
+    let numbers = numbers.filter(|_| parts[0].trim().len() == 4);
+
+    let period = match numbers.as_deref() {
+        Some(&[year]) => NaiveDate::from_ymd_opt(year as i32, 1, 1)
+            .zip(NaiveDate::from_ymd_opt(year as i32, 12, 31)),
+        Some(&[year, month]) => NaiveDate::from_ymd_opt(year as i32, month, 1)
+            .and_then(|start| Some((start, last_day_of_month(start)?))),
+        Some(&[year, month, day]) => {
+            NaiveDate::from_ymd_opt(year as i32, month, day).map(|date| (date, date))
+        }
+        _ => None,
+    };
+
+    match period {
+        Some(period) => Ok(period),
+        None => bail!(t!("time_range.error_date", input)),
+    }
+}
+
+fn last_day_of_month(first: NaiveDate) -> Option<NaiveDate> {
+    first.checked_add_months(chrono::Months::new(1))?.pred_opt()
+}
+
+/// N 天前的时间戳，天数过大时停在最早的时间
+fn days_ago(now: i64, days: i64) -> i64 {
+    days.checked_mul(86400)
+        .and_then(|seconds| now.checked_sub(seconds))
+        .unwrap_or(i64::MIN)
+}
+
+fn day_start(date: NaiveDate) -> i64 {
+    date.and_hms_opt(0, 0, 0)
+        .map(|dt| dt.and_utc().timestamp())
+        .unwrap_or(i64::MIN)
+}
+
+fn day_end(date: NaiveDate) -> i64 {
+    date.succ_opt().map(day_start).unwrap_or(i64::MAX)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn date(year: i32, month: u32, day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(year, month, day).unwrap()
+    }
+
+    #[test]
+    fn parses_day_counts() {
+        assert_eq!(TimeRange::parse("30d").unwrap(), TimeRange::OlderThan(30));
+        assert_eq!(TimeRange::parse(">30d").unwrap(), TimeRange::OlderThan(30));
+        assert_eq!(TimeRange::parse("older 30").unwrap(), TimeRange::OlderThan(30));
+        assert_eq!(TimeRange::parse("<7d").unwrap(), TimeRange::NewerThan(7));
+        assert_eq!(TimeRange::parse("newer 7").unwrap(), TimeRange::NewerThan(7));
+        assert_eq!(TimeRange::parse("90天").unwrap(), TimeRange::OlderThan(90));
+        assert_eq!(TimeRange::parse(" ALL ").unwrap(), TimeRange::All);
+        assert_eq!(TimeRange::parse("").unwrap(), TimeRange::All);
+    }
+
+    #[test]
+    fn bare_numbers_after_comparison_are_years() {
+        assert_eq!(TimeRange::parse("<2023").unwrap(), TimeRange::Before(date(2023, 1, 1)));
+        assert_eq!(TimeRange::parse(">2023").unwrap(), TimeRange::After(date(2023, 12, 31)));
+        assert!(TimeRange::parse("<30").is_err());
+    }
+
+    #[test]
+    fn parses_dates_and_periods() {
+        assert_eq!(
+            TimeRange::parse("before 2023-01-15").unwrap(),
+            TimeRange::Before(date(2023, 1, 15))
+        );
+        assert_eq!(TimeRange::parse(">2023-02").unwrap(), TimeRange::After(date(2023, 2, 28)));
+        assert_eq!(
+            TimeRange::parse("2024-02").unwrap(),
+            TimeRange::Between(date(2024, 2, 1), date(2024, 2, 29))
+        );
+        assert_eq!(
+            TimeRange::parse("2022-03..2022-06").unwrap(),
+            TimeRange::Between(date(2022, 3, 1), date(2022, 6, 30))
+        );
+        assert_eq!(
+            TimeRange::parse("between 2021 and 2022-01-10").unwrap(),
+            TimeRange::Between(date(2021, 1, 1), date(2022, 1, 10))
+        );
+    }
+
+    #[test]
+    fn rejects_invalid_input() {
+        assert!(TimeRange::parse("2022-13").is_err());
+        assert!(TimeRange::parse("2023..2022").is_err());
+        assert!(TimeRange::parse("yesterday").is_err());
+        assert!(TimeRange::parse("22-03").is_err());
+    }
+
+    #[test]
+    fn huge_day_counts_do_not_overflow() {
+        assert!(!TimeRange::OlderThan(i64::MAX).should_delete(0));
+        assert!(TimeRange::NewerThan(i64::MAX).should_delete(0));
+    }
+
+    #[test]
+    fn date_bounds_are_exclusive_or_inclusive_as_documented() {
+        let start = day_start(date(2023, 1, 1));
+        assert!(TimeRange::Before(date(2023, 1, 1)).should_delete(start - 1));
+        assert!(!TimeRange::Before(date(2023, 1, 1)).should_delete(start));
+        assert!(!TimeRange::After(date(2023, 1, 1)).should_delete(start + 86399));
+        assert!(TimeRange::After(date(2023, 1, 1)).should_delete(start + 86400));
+
+        let range = TimeRange::Between(date(2023, 1, 1), date(2023, 1, 31));
+        assert!(range.should_delete(start));
+        assert!(range.should_delete(day_start(date(2023, 1, 31)) + 86399));
+        assert!(!range.should_delete(day_start(date(2023, 2, 1))));
+    }
+}
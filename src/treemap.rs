@@ -0,0 +1,135 @@
+use ratatui::layout::Rect;
+
+/// 矩形树图中的一块
+#[derive(Debug, Clone)]
+pub struct Tile {
+    pub label: String,
+    pub size: u64,
+    pub target: TileTarget,
+}
+
+/// 树图块对应的内容，决定回车后进入哪一层
+#[derive(Debug, Clone, PartialEq)]
+pub enum TileTarget {
+    Group(String),   // 群号
+    Year(i32),       // 当前群的某一年
+    Month(i32, u32), // 当前群某一年的某个月
+    Others,          // 放不下的小群合计
+}
+
+/// 按大小把区域划分给各块，`sizes` 需要从大到小排列
+///
+/// 每次把列表分成大小最接近的两半，沿较长的一边按比例切开。
+/// 终端字符高约为宽的两倍，比较长短时高度按两倍计算。
+/// 太小放不下的块得到空区域。
+pub fn layout(sizes: &[u64], area: Rect) -> Vec<Rect> {
+    let mut rects = vec![Rect::default(); sizes.len()];
+    split(sizes, area, &mut rects);
+    rects
+}
+
+fn split(sizes: &[u64], area: Rect, out: &mut [Rect]) {
+    if sizes.is_empty() || area.is_empty() {
+        return;
+    }
+    if sizes.len() == 1 {
+        out[0] = area;
+        return;
+    }
+
+    let total: u64 = sizes.iter().sum();
+    if total == 0 {
+        return;
+    }
+
+    let mut cut = 1;
+    let mut first_size = sizes[0];
+    while cut < sizes.len() - 1 && (first_size + sizes[cut]) * 2 <= total {
+        first_size += sizes[cut];
+        cut += 1;
+    }
+
+    let horizontal = area.width >= area.height * 2;
+    let length = if horizontal { area.width } else { area.height };
+    if length < 2 {
+        // 这一边已经无法再分，全部给较大的一半
+        split(&sizes[..cut], area, &mut out[..cut]);
+        return;
+    }
+
+    let first_length = ((length as u64 * first_size + total / 2) / total).clamp(1, length as u64 - 1) as u16;
+    let (first, second) = if horizontal {
+        (
+            Rect::new(area.x, area.y, first_length, area.height),
+            Rect::new(area.x + first_length, area.y, area.width - first_length, area.height),
+        )
+    } else {
+        (
+            Rect::new(area.x, area.y, area.width, first_length),
+            Rect::new(area.x, area.y + first_length, area.width, area.height - first_length),
+        )
+    };
+
+    let (first_out, second_out) = out.split_at_mut(cut);
+    split(&sizes[..cut], first, first_out);
+    split(&sizes[cut..], second, second_out);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(rect: &Rect) -> u32 {
+        rect.width as u32 * rect.height as u32
+    }
+
+    fn overlaps(a: &Rect, b: &Rect) -> bool {
+        !a.intersection(*b).is_empty()
+    }
+
+    #[test]
+    fn single_tile_fills_the_area() {
+        let full = Rect::new(2, 3, 40, 10);
+        assert_eq!(layout(&[100], full), vec![full]);
+    }
+
+    #[test]
+    fn tiles_cover_the_area_without_overlap() {
+        let full = Rect::new(0, 0, 80, 20);
+        let rects = layout(&[50, 30, 10, 5, 5], full);
+
+        assert_eq!(rects.iter().map(area).sum::<u32>(), area(&full));
+        for (i, a) in rects.iter().enumerate() {
+            assert_eq!(full.intersection(*a), *a);
+            for b in &rects[i + 1..] {
+                assert!(!overlaps(a, b), "{:?} {:?}", a, b);
+            }
+        }
+    }
+
+    #[test]
+    fn areas_follow_sizes() {
+        let rects = layout(&[3, 1], Rect::new(0, 0, 80, 20));
+        // 宽是高的两倍以上，沿宽度切开
+        assert_eq!(rects[0], Rect::new(0, 0, 60, 20));
+        assert_eq!(rects[1], Rect::new(60, 0, 20, 20));
+
+        let rects = layout(&[1, 1], Rect::new(0, 0, 10, 20));
+        assert_eq!(rects[0], Rect::new(0, 0, 10, 10));
+        assert_eq!(rects[1], Rect::new(0, 10, 10, 10));
+    }
+
+    #[test]
+    fn tiny_areas_leave_small_tiles_empty() {
+        let rects = layout(&[10, 1, 1], Rect::new(0, 0, 1, 1));
+        assert_eq!(rects[0], Rect::new(0, 0, 1, 1));
+        assert!(rects[1..].iter().all(|r| r.is_empty()));
+    }
+
+    #[test]
+    fn empty_inputs() {
+        assert!(layout(&[], Rect::new(0, 0, 10, 10)).is_empty());
+        assert!(layout(&[0, 0], Rect::new(0, 0, 10, 10)).iter().all(|r| r.is_empty()));
+        assert!(layout(&[5], Rect::default()).iter().all(|r| r.is_empty()));
+    }
+}
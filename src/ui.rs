@@ -1,35 +1,228 @@
 use ratatui::{
-    layout::{Alignment, Constraint, Direction, Layout, Rect},
+    layout::{Alignment, Constraint, Direction, Layout, Position, Rect},
     style::{Color, Modifier, Style},
     text::{Line, Span},
-    widgets::{BarChart, Block, Borders, Cell, Clear, Paragraph, Row, Table, Tabs, Wrap},
+    widgets::{BarChart, Block, Borders, Cell, Clear, Paragraph, Row, Sparkline, Table, Tabs, Wrap},
     Frame,
 };
 
-use crate::app::{App, AppTab};
-use crate::models::format_bytes;
-
-pub fn draw(f: &mut Frame, app: &App) {
-    let chunks = Layout::default()
+use crate::app::{
+    ActivityFilter, App, AppTab, ChartMetric, ChartView, FilterOption, LogLevel, QuitFilter, SortBy,
+};
+use chrono::NaiveDate;
+use crate::keymap::{Action, KeyContext};
+use crate::file_filter::{AnimationFilter, OriginalFilter};
+use crate::policy::GroupPolicy;
+use crate::models::{format_bytes, CleanScope, GroupStats};
+use crate::search::fuzzy_match;
+use std::rc::Rc;
+
+/// 顶部标签、主体和底部状态栏
+fn root_chunks(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
         .direction(Direction::Vertical)
         .constraints([
             Constraint::Length(3),
             Constraint::Min(0),
             Constraint::Length(1),
         ])
-        .split(f.area());
+        .split(area)
+}
+
+/// 清理/迁移页的左侧列表和右侧配置
+fn list_and_side(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area)
+}
+
+/// 文件列表打开时，右侧上方显示图片预览，下方保留配置
+fn preview_and_options(area: Rect) -> Rc<[Rect]> {
+    Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
+        .split(area)
+}
+
+fn shows_preview(app: &App) -> bool {
+    app.file_view.is_some()
+        && app.preview_enabled
+        && matches!(app.current_tab, AppTab::Clean | AppTab::Migrate)
+}
+
+/// 图片预览的内部区域（不含边框），终端图形协议按此位置输出
+pub fn preview_area(area: Rect, app: &App) -> Option<Rect> {
+    if !shows_preview(app) {
+        return None;
+    }
+
+    let body = root_chunks(area)[1];
+    let side = list_and_side(body)[1];
+    let preview = preview_and_options(side)[0];
+    Some(Block::default().borders(Borders::ALL).inner(preview))
+}
+
+/// 列表滚动位置：让当前行尽量保持在可见区域中间
+fn scroll_offset(selected: usize, total: usize, visible_height: usize) -> usize {
+    if total == 0 || selected < visible_height / 2 {
+        0
+    } else if selected >= total.saturating_sub(visible_height / 2) {
+        total.saturating_sub(visible_height)
+    } else {
+        selected.saturating_sub(visible_height / 2)
+    }
+}
+
+/// 鼠标位置对应的标签页下标（与 Tabs 组件的绘制方式一致：标题两侧各留一格，标题间有一格分隔符）
+pub fn tab_at(area: Rect, column: u16, row: u16) -> Option<usize> {
+    let header = root_chunks(area)[0];
+    let inner = Block::default().borders(Borders::ALL).inner(header);
+    if !inner.contains(Position::new(column, row)) {
+        return None;
+    }
+
+    let mut x = inner.x;
+    for (idx, title) in AppTab::titles().iter().enumerate() {
+        let width = Line::from(*title).width() as u16 + 2;
+        if column >= x && column < x + width {
+            return Some(idx);
+        }
+        x += width + 1;
+    }
+    None
+}
+
+/// 鼠标位置对应的列表行：返回 (列表中的下标, 是否点在复选框列上)
+///
+/// 文件列表打开时对应文件列表，否则对应群组列表。
+pub fn list_row_at(area: Rect, app: &App, column: u16, row: u16) -> Option<(usize, bool)> {
+    if !matches!(app.current_tab, AppTab::Clean | AppTab::Migrate) {
+        return None;
+    }
+
+    let list = list_and_side(root_chunks(area)[1])[0];
+    let inner = Block::default().borders(Borders::ALL).inner(list);
+    if !inner.contains(Position::new(column, row)) {
+        return None;
+    }
+
+    let (first_row, selected, total, visible_height) = match app.file_view.as_ref() {
+        // 文件列表有一行表头
+        Some(view) => (
+            inner.y + 1,
+            view.selected_index,
+            view.order.len(),
+            (list.height as usize).saturating_sub(3),
+        ),
+        None => (
+            inner.y,
+            app.selected_index,
+            app.filtered_stats.len(),
+            (list.height as usize).saturating_sub(2),
+        ),
+    };
+
+    if row < first_row {
+        return None;
+    }
+
+    let index = scroll_offset(selected, total, visible_height) + (row - first_row) as usize;
+    if index >= total {
+        return None;
+    }
+
+    Some((index, column < inner.x + 3))
+}
+
+/// 对话框底部居中排列的按钮区域，绘制和鼠标点击共用
+fn dialog_buttons(dialog: Rect, labels: &[String]) -> Vec<Rect> {
+    const GAP: u16 = 3;
+
+    let inner = Block::default().borders(Borders::ALL).inner(dialog);
+    let widths: Vec<u16> = labels.iter().map(|l| Line::from(l.as_str()).width() as u16).collect();
+    let total: u16 = widths.iter().sum::<u16>() + GAP * (labels.len().saturating_sub(1) as u16);
+    let y = inner.y + inner.height.saturating_sub(1);
+    let mut x = inner.x + inner.width.saturating_sub(total) / 2;
+
+    widths
+        .iter()
+        .map(|&width| {
+            let rect = Rect::new(x, y, width, 1).intersection(inner);
+            x += width + GAP;
+            rect
+        })
+        .collect()
+}
+
+/// 按键提示，例如 "[d/Delete]"
+fn key_hint(app: &App, action: Action) -> String {
+    format!("[{}]", app.keymap.label(action))
+}
+
+fn confirm_button_labels(app: &App) -> [String; 2] {
+    [
+        t!("ui.button_confirm", key_hint(app, Action::Confirm)),
+        t!("ui.button_cancel", key_hint(app, Action::Cancel)),
+    ]
+}
+
+fn filter_button_labels(app: &App) -> [String; 2] {
+    [
+        t!("ui.button_apply", key_hint(app, Action::Apply)),
+        t!("ui.button_cancel_filter", key_hint(app, Action::Cancel)),
+    ]
+}
+
+fn confirm_dialog_area(area: Rect) -> Rect {
+    centered_rect(70, 40, area)
+}
+
+fn filter_dialog_area(area: Rect) -> Rect {
+    centered_rect(80, 85, area)
+}
+
+/// 确认对话框的 [确认, 取消] 按钮区域
+pub fn confirm_buttons(area: Rect, app: &App) -> Vec<Rect> {
+    dialog_buttons(confirm_dialog_area(area), &confirm_button_labels(app))
+}
+
+/// 过滤器对话框的 [应用, 取消] 按钮区域
+pub fn filter_buttons(area: Rect, app: &App) -> Vec<Rect> {
+    dialog_buttons(filter_dialog_area(area), &filter_button_labels(app))
+}
+
+/// 鼠标位置对应的过滤器选项下标
+pub fn filter_option_at(area: Rect, app: &App, row: u16) -> Option<usize> {
+    let dialog = filter_dialog_area(area);
+    let inner = Block::default().borders(Borders::ALL).inner(dialog);
+    let line = row.checked_sub(inner.y)? as usize;
+
+    let (_, option_lines) = filter_dialog_lines(app, inner.width.saturating_sub(2) as usize);
+    option_lines.iter().position(|&l| l == line)
+}
+
+fn render_dialog_buttons(f: &mut Frame, dialog: Rect, labels: &[String], styles: &[Style]) {
+    for ((rect, label), style) in dialog_buttons(dialog, labels).into_iter().zip(labels).zip(styles) {
+        f.render_widget(Paragraph::new(label.as_str()).style(*style), rect);
+    }
+}
+
+pub fn draw(f: &mut Frame, app: &App) {
+    let chunks = root_chunks(f.area());
 
     render_header(f, app, chunks[0]);
     match app.current_tab {
         AppTab::Analysis => render_analysis(f, app, chunks[1]),
         AppTab::Clean => render_clean(f, app, chunks[1]),
         AppTab::Migrate => render_migrate(f, app, chunks[1]),
+        AppTab::Logs => render_logs(f, app, chunks[1]),
     }
 
     render_status(f, app, chunks[2]);
 
     if app.show_help {
-        render_help_dialog(f);
+        render_help_dialog(f, app);
     }
 
     if app.show_confirm_dialog {
@@ -63,27 +256,104 @@ fn render_header(f: &mut Frame, app: &App, area: Rect) {
 }
 
 fn render_status(f: &mut Frame, app: &App, area: Rect) {
-    let status_text = if app.progress.is_running {
-        format!(
-            "进行中: {}/{} | 当前: {} | [q]退出 [?]帮助",
-            app.progress.current, app.progress.total, app.progress.current_file
+    let status_text = if let Some(ref input) = app.time_input {
+        match app.time_input_error {
+            Some(ref error) => t!("ui.status_time_input_error", input, error),
+            None => t!("ui.status_time_input", input),
+        }
+    } else if let Some(ref input) = app.budget_input {
+        match app.budget_input_error {
+            Some(ref error) => t!("ui.status_budget_input_error", input, error),
+            None => t!("ui.status_budget_input", input, app.pinned_groups.len()),
+        }
+    } else if app.search_active {
+        t!("ui.status_search", app.search_query)
+    } else if app.progress.is_running {
+        t!(
+            "ui.status_running",
+            app.progress.current,
+            app.progress.total,
+            app.progress.current_file,
+            key_hint(app, Action::Quit),
+            key_hint(app, Action::Help)
         )
     } else {
-        format!(
-            "群组: {} | 已选: {} | 总大小: {} | [q]退出 [?]帮助 [Tab]切换",
+        t!(
+            "ui.status_idle",
             app.filtered_stats.len(),
             app.selected_count(),
-            format_bytes(app.selected_total_size())
+            format_bytes(app.selected_total_size()),
+            key_hint(app, Action::Quit),
+            key_hint(app, Action::Help),
+            key_hint(app, Action::NextTab)
         )
     };
 
+    let color = if app.time_input_error.is_some() || app.budget_input_error.is_some() {
+        Color::Red
+    } else {
+        Color::White
+    };
     let status = Paragraph::new(status_text)
-        .style(Style::default().fg(Color::White))
+        .style(Style::default().fg(color))
         .alignment(Alignment::Left);
 
     f.render_widget(status, area);
 }
 
+fn render_logs(f: &mut Frame, app: &App, area: Rect) {
+    let entries = app.visible_logs();
+    let visible_height = (area.height as usize).saturating_sub(2);
+
+    // log_scroll 从最新一条往回数，最新的日志显示在底部
+    let end = entries.len().saturating_sub(app.log_scroll);
+    let start = end.saturating_sub(visible_height);
+
+    let lines: Vec<Line> = entries[start..end]
+        .iter()
+        .map(|entry| {
+            let (level_style, message_style) = match entry.level {
+                LogLevel::Info => (Style::default().fg(Color::Cyan), Style::default()),
+                LogLevel::Success => (Style::default().fg(Color::Green), Style::default()),
+                LogLevel::Warning => (
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD),
+                    Style::default().fg(Color::Yellow),
+                ),
+                LogLevel::Error => (
+                    Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+                    Style::default().fg(Color::Red),
+                ),
+            };
+
+            Line::from(vec![
+                Span::styled(format!("{} ", entry.time), Style::default().fg(Color::DarkGray)),
+                Span::styled(format!("{:5} ", entry.level.label()), level_style),
+                Span::styled(entry.message.clone(), message_style),
+            ])
+        })
+        .collect();
+
+    let position = if app.log_scroll > 0 {
+        t!("ui.log_scrolled", app.log_scroll)
+    } else {
+        String::new()
+    };
+
+    let title = t!(
+        "ui.log_title",
+        entries.len(),
+        app.logs.len(),
+        app.log_filter.description(),
+        position,
+        key_hint(app, Action::LogFilter),
+        key_hint(app, Action::Bottom)
+    );
+
+    let paragraph = Paragraph::new(lines).block(Block::default().borders(Borders::ALL).title(title));
+
+    f.render_widget(paragraph, area);
+}
+
 fn render_analysis(f: &mut Frame, app: &App, area: Rect) {
     let chunks = Layout::default()
         .direction(Direction::Vertical)
@@ -94,44 +364,260 @@ fn render_analysis(f: &mut Frame, app: &App, area: Rect) {
         ])
         .split(area);
 
-    render_top_groups(f, app, chunks[0]);
+    let top = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(40), Constraint::Percentage(60)])
+        .split(chunks[0]);
+
+    render_top_groups(f, app, top[0]);
+    render_treemap(f, app, top[1]);
     render_time_distribution(f, app, chunks[1]);
-    render_statistics_summary(f, app, chunks[2]);
+
+    let bottom = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(45), Constraint::Percentage(55)])
+        .split(chunks[2]);
+
+    render_statistics_summary(f, app, bottom[0]);
+    render_reclaim_projection(f, app, bottom[1]);
 }
 
 fn render_clean(f: &mut Frame, app: &App, area: Rect) {
-    let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(area);
+    let chunks = list_and_side(area);
+
+    if app.file_view.is_some() {
+        render_file_list(f, app, chunks[0]);
+    } else {
+        render_group_list(f, app, chunks[0], t!("ui.clean_list_title"));
+    }
 
-    render_group_list(f, app, chunks[0], "选择要清理的群组");
-    render_clean_options(f, app, chunks[1]);
+    render_side(f, app, chunks[1], render_clean_options);
 }
 
 fn render_migrate(f: &mut Frame, app: &App, area: Rect) {
+    let chunks = list_and_side(area);
+
+    if app.file_view.is_some() {
+        render_file_list(f, app, chunks[0]);
+    } else {
+        render_group_list(f, app, chunks[0], t!("ui.migrate_list_title"));
+    }
+
+    render_side(f, app, chunks[1], render_migrate_options);
+}
+
+/// 右侧区域：文件列表上方显示图片预览，群组列表上方显示当前群的详情
+fn render_side(f: &mut Frame, app: &App, area: Rect, render_options: fn(&mut Frame, &App, Rect)) {
+    if shows_preview(app) {
+        let side = preview_and_options(area);
+        render_preview(f, app, side[0]);
+        render_options(f, app, side[1]);
+    } else if app.file_view.is_none() && app.details_enabled && app.current_group().is_some() {
+        let side = preview_and_options(area);
+        render_group_details(f, app, side[0]);
+        render_options(f, app, side[1]);
+    } else {
+        render_options(f, app, area);
+    }
+}
+
+fn render_group_details(f: &mut Frame, app: &App, area: Rect) {
+    use chrono::{DateTime, Datelike, Utc};
+
+    let Some(stat) = app.current_group() else {
+        return;
+    };
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(t!("ui.details_title", truncate(&stat.group_name, 24), key_hint(app, Action::Details)));
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let field = |label: &str, value: String, color: Color| {
+        Line::from(vec![
+            Span::styled(label.to_string(), Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(value, Style::default().fg(color)),
+        ])
+    };
+    let info = stat.info.as_ref();
+    let dash = || "-".to_string();
+
+    let status = match info {
+        None => (dash(), Color::DarkGray),
+        Some(_) if stat.has_quit() => (t!("ui.detail_status_quit").to_string(), Color::Red),
+        Some(_) => (t!("ui.detail_status_member").to_string(), Color::Green),
+    };
+
+    let first_time = stat.files.iter().map(|f| f.msg_time).min();
+    let last_time = stat.files.iter().map(|f| f.msg_time).max();
+    let media_range = match (first_time, last_time) {
+        (Some(first), Some(last)) => format!("{} ~ {}", format_date(first), format_date(last)),
+        _ => t!("ui.detail_no_files").to_string(),
+    };
+
+    let ori_size: u64 = stat.files.iter().filter_map(|f| f.ori_size).sum();
+    let thumb_size: u64 = stat.files.iter().filter_map(|f| f.thumb_size).sum();
+    let media_total = ori_size + thumb_size;
+    let percent = |size: u64| (size * 100).checked_div(media_total).unwrap_or(0);
+
+    let mut lines = vec![
+        field(t!("ui.detail_id"), stat.group_id.clone(), Color::Cyan),
+        field(t!("ui.detail_remark"), stat.group_remark().map(str::to_string).unwrap_or_else(dash), Color::Cyan),
+        field(
+            t!("ui.detail_members"),
+            info.map(|i| format!("{}/{}", i.member_count, i.max_member)).unwrap_or_else(dash),
+            Color::Cyan,
+        ),
+        field(t!("ui.detail_created"), info.map(|i| format_date(i.create_time)).unwrap_or_else(dash), Color::Cyan),
+        field(
+            t!("ui.detail_owner"),
+            info.map(|i| i.owner_uid.clone()).filter(|uid| !uid.is_empty()).unwrap_or_else(dash),
+            Color::Cyan,
+        ),
+        field(t!("ui.detail_status"), status.0, status.1),
+        field(t!("ui.detail_media_range"), media_range, Color::Cyan),
+        field(
+            t!("ui.detail_ori_thumb"),
+            t!(
+                "ui.detail_ori_thumb_value",
+                format_bytes(ori_size),
+                percent(ori_size),
+                format_bytes(thumb_size),
+                percent(thumb_size)
+            ),
+            Color::Cyan,
+        ),
+    ];
+
+    // 原图与缩略图占比条
+    let bar_width = inner.width as u64;
+    if media_total > 0 && bar_width > 0 {
+        let ori_width = (ori_size * bar_width / media_total) as usize;
+        lines.push(Line::from(vec![
+            Span::styled("█".repeat(ori_width), Style::default().fg(Color::Green)),
+            Span::styled("█".repeat(bar_width as usize - ori_width), Style::default().fg(Color::Blue)),
+        ]));
+    }
+
+    // 按月（UTC，与时间分布图一致）统计现存文件大小，中间没有文件的月份补 0
+    let month_index = |timestamp: i64| {
+        DateTime::<Utc>::from_timestamp(timestamp, 0).map(|dt| dt.year() * 12 + dt.month0() as i32)
+    };
+    let months: Vec<(i32, u64)> = stat
+        .files
+        .iter()
+        .filter_map(|f| Some((month_index(f.msg_time)?, f.actual_size?)))
+        .collect();
+    let first_month = months.iter().map(|&(m, _)| m).min();
+    let last_month = months.iter().map(|&(m, _)| m).max();
+
+    let mut monthly = Vec::new();
+    if let (Some(first), Some(last)) = (first_month, last_month) {
+        monthly = vec![0u64; (last - first + 1) as usize];
+        for (month, size) in months {
+            monthly[(month - first) as usize] += size;
+        }
+        // 只显示放得下的最近几个月
+        let skip = monthly.len().saturating_sub(inner.width as usize);
+        monthly.drain(..skip);
+
+        let label = |m: i32| format!("{}-{:02}", m.div_euclid(12), m.rem_euclid(12) + 1);
+        lines.push(Line::from(""));
+        lines.push(Line::from(Span::styled(
+            t!(
+                "ui.detail_monthly",
+                label(last - monthly.len() as i32 + 1),
+                label(last),
+                format_bytes(monthly.iter().copied().max().unwrap_or(0))
+            ),
+            Style::default().fg(Color::DarkGray),
+        )));
+    }
+
     let chunks = Layout::default()
-        .direction(Direction::Horizontal)
-        .constraints([Constraint::Percentage(60), Constraint::Percentage(40)])
-        .split(area);
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(lines.len() as u16), Constraint::Min(0)])
+        .split(inner);
+
+    f.render_widget(Paragraph::new(lines), chunks[0]);
+    if !monthly.is_empty() {
+        let sparkline = Sparkline::default()
+            .data(&monthly)
+            .style(Style::default().fg(Color::Cyan));
+        f.render_widget(sparkline, chunks[1]);
+    }
+}
+
+fn format_date(timestamp: i64) -> String {
+    use chrono::{Local, TimeZone};
 
-    render_group_list(f, app, chunks[0], "选择要迁移的群组");
-    render_migrate_options(f, app, chunks[1]);
+    Local
+        .timestamp_opt(timestamp, 0)
+        .single()
+        .filter(|_| timestamp > 0)
+        .map(|dt| dt.format("%Y-%m-%d").to_string())
+        .unwrap_or_else(|| "-".to_string())
+}
+
+fn render_preview(f: &mut Frame, app: &App, area: Rect) {
+    use crate::preview::PreviewContent;
+
+    let file_name = app
+        .preview
+        .source
+        .as_ref()
+        .and_then(|p| p.file_name())
+        .map(|n| n.to_string_lossy().into_owned())
+        .unwrap_or_default();
+    let title = t!(
+        "ui.preview_title",
+        app.preview.protocol.description(),
+        truncate(&file_name, 24),
+        key_hint(app, Action::Preview)
+    );
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    match &app.preview.content {
+        Some(PreviewContent::HalfBlock(rows)) => {
+            let lines: Vec<Line> = rows
+                .iter()
+                .map(|row| {
+                    Line::from(
+                        row.iter()
+                            .map(|&(top, bottom)| {
+                                Span::styled("▀", Style::default().fg(top).bg(bottom))
+                            })
+                            .collect::<Vec<_>>(),
+                    )
+                })
+                .collect();
+            f.render_widget(Paragraph::new(lines).block(block), area);
+        }
+        // 图片由终端图形协议在绘制后直接输出，这里只绘制边框
+        Some(PreviewContent::Escape(_)) => f.render_widget(block, area),
+        Some(PreviewContent::Error(e)) => {
+            let paragraph = Paragraph::new(e.as_str())
+                .style(Style::default().fg(Color::Red))
+                .block(block)
+                .wrap(Wrap { trim: true });
+            f.render_widget(paragraph, area);
+        }
+        None => {
+            let message = if app.preview.is_loading() { t!("ui.image_loading") } else { t!("ui.image_missing") };
+            let paragraph = Paragraph::new(message)
+                .style(Style::default().fg(Color::DarkGray))
+                .block(block)
+                .alignment(Alignment::Center);
+            f.render_widget(paragraph, area);
+        }
+    }
 }
 
 fn render_group_list(f: &mut Frame, app: &App, area: Rect, title: &str) {
     let visible_height = (area.height as usize).saturating_sub(2);
     let total_items = app.filtered_stats.len();
-
-    let scroll_offset = if total_items == 0 {
-        0
-    } else if app.selected_index < visible_height / 2 {
-        0
-    } else if app.selected_index >= total_items.saturating_sub(visible_height / 2) {
-        total_items.saturating_sub(visible_height)
-    } else {
-        app.selected_index.saturating_sub(visible_height / 2)
-    };
+    let scroll_offset = scroll_offset(app.selected_index, total_items, visible_height);
 
     let visible_end = (scroll_offset + visible_height).min(total_items);
 
@@ -147,11 +633,6 @@ fn render_group_list(f: &mut Frame, app: &App, area: Rect, title: &str) {
             let is_current = list_idx == app.selected_index;
 
             let checkbox = if is_selected { "[x]" } else { "[ ]" };
-            let group_display = if stat.group_name != format!("群 {}", stat.group_id) {
-                format!("{} ({})", stat.group_name, stat.group_id)
-            } else {
-                stat.group_id.clone()
-            };
 
             let checkbox_style = if is_selected {
                 Style::default()
@@ -187,15 +668,47 @@ fn render_group_list(f: &mut Frame, app: &App, area: Rect, title: &str) {
             let exist_count_in_range = app.group_exist_count_in_range(stat);
             let file_count_in_range = app.group_file_count_in_range(stat);
 
+            let query = app.search_query.as_str();
+            let mut group_display = Vec::new();
+            if stat.group_name != t!("models.unnamed_group", stat.group_id) {
+                group_display.extend(highlight_matches(&stat.group_name, query, name_style));
+                group_display.push(Span::styled(" (", name_style));
+                group_display.extend(highlight_matches(&stat.group_id, query, name_style));
+                group_display.push(Span::styled(")", name_style));
+            } else {
+                group_display.extend(highlight_matches(&stat.group_id, query, name_style));
+            }
+            if stat.has_quit() {
+                group_display.push(Span::styled(
+                    t!("ui.quit_tag"),
+                    Style::default().fg(Color::Red),
+                ));
+            }
+            if app.pinned_groups.contains(&stat.group_id) {
+                group_display.push(Span::styled(
+                    t!("ui.pinned_tag"),
+                    Style::default().fg(Color::Cyan),
+                ));
+            }
+            // 仅在备注命中搜索时显示备注，避免挤占群名
+            if let Some(remark) = stat.group_remark() {
+                if !query.is_empty() && fuzzy_match(query, remark).is_some() {
+                    group_display.push(Span::styled(t!("ui.remark_prefix"), count_style));
+                    group_display.extend(highlight_matches(remark, query, count_style));
+                    group_display.push(Span::styled("]", count_style));
+                }
+            }
+
             Row::new(vec![
                 Cell::from(checkbox).style(checkbox_style),
-                Cell::from(group_display).style(name_style),
+                Cell::from(Line::from(group_display)),
                 Cell::from(format_bytes(size_in_range)).style(Style::default().fg(Color::Cyan)),
                 Cell::from(format!(
                     "({}/{})",
                     exist_count_in_range, file_count_in_range
                 ))
                 .style(count_style),
+                Cell::from(sort_value(app, stat)).style(count_style),
             ])
             .style(row_style)
         })
@@ -207,15 +720,21 @@ fn render_group_list(f: &mut Frame, app: &App, area: Rect, title: &str) {
         String::new()
     };
 
-    let sort_text = match app.sort_by {
-        crate::app::SortBy::Size => "大小",
-        crate::app::SortBy::FileCount => "文件数",
-        crate::app::SortBy::Name => "名称",
+    let sort_text = format!("{}{}", app.sort_by.description(), app.sort_arrow());
+
+    let search_text = if app.search_query.is_empty() {
+        String::new()
+    } else {
+        t!("ui.search_tag", app.search_query)
     };
 
-    let help_text = format!(
-        " {}{} [排序:{}] [?]帮助",
-        title, scroll_indicator, sort_text
+    let help_text = t!(
+        "ui.group_list_title",
+        title,
+        scroll_indicator,
+        sort_text,
+        search_text,
+        key_hint(app, Action::Help)
     );
 
     let table = Table::new(
@@ -225,6 +744,7 @@ fn render_group_list(f: &mut Frame, app: &App, area: Rect, title: &str) {
             Constraint::Min(10),    // 群名（自适应剩余空间）
             Constraint::Length(10), // 大小
             Constraint::Length(12), // 文件数
+            Constraint::Length(sort_column_width(app)), // 当前排序依据的值
         ],
     )
     .block(Block::default().borders(Borders::ALL).title(help_text))
@@ -233,14 +753,164 @@ fn render_group_list(f: &mut Frame, app: &App, area: Rect, title: &str) {
     f.render_widget(table, area);
 }
 
+/// 排序依据不在已有列中时，额外显示它的值
+fn sort_value(app: &App, stat: &GroupStats) -> String {
+    match app.sort_by {
+        SortBy::LastActivity => format_date(stat.last_activity()),
+        SortBy::MissingCount => t!("ui.missing_value", stat.missing_count),
+        SortBy::MemberCount => match stat.info {
+            Some(ref info) => t!("ui.member_value", info.member_count),
+            None => "-".to_string(),
+        },
+        SortBy::CreateTime => format_date(stat.info.as_ref().map(|info| info.create_time).unwrap_or(0)),
+        SortBy::Size | SortBy::FileCount | SortBy::Name => String::new(),
+    }
+}
+
+fn sort_column_width(app: &App) -> u16 {
+    match app.sort_by {
+        SortBy::Size | SortBy::FileCount | SortBy::Name => 0,
+        _ => 12,
+    }
+}
+
+fn render_file_list(f: &mut Frame, app: &App, area: Rect) {
+    use chrono::{Local, TimeZone};
+
+    let (Some(view), Some(stat_idx)) = (app.file_view.as_ref(), app.file_view_stat_index()) else {
+        return;
+    };
+    let stat = &app.stats[stat_idx];
+    let filter = app.rule_filter_for(stat);
+
+    let visible_height = (area.height as usize).saturating_sub(3);
+    let total_items = view.order.len();
+    let scroll_offset = scroll_offset(view.selected_index, total_items, visible_height);
+
+    let header = Row::new(
+        ["", t!("ui.col_file_name"), t!("file_sort.time"), t!("sort.size"), t!("ui.col_ori"), t!("ui.col_thumb"), t!("ui.col_original"), t!("ui.col_sender"), t!("file_sort.media_type")]
+            .iter()
+            .map(|h| {
+                Cell::from(*h).style(
+                    Style::default()
+                        .fg(Color::Yellow)
+                        .add_modifier(Modifier::BOLD),
+                )
+            }),
+    );
+
+    let rows: Vec<Row> = view
+        .order
+        .iter()
+        .enumerate()
+        .skip(scroll_offset)
+        .take(visible_height)
+        .map(|(list_idx, &file_idx)| {
+            let file = &stat.files[file_idx];
+            let is_selected = app.is_file_selected(&stat.group_id, file);
+            let is_current = list_idx == view.selected_index;
+            let in_range = filter.matches(file);
+
+            let checkbox = if is_selected { "[x]" } else { "[ ]" };
+            let time = Local
+                .timestamp_opt(file.msg_time, 0)
+                .single()
+                .map(|t| t.format("%Y-%m-%d %H:%M").to_string())
+                .unwrap_or_default();
+            let size = match file.actual_size {
+                Some(size) => format_bytes(size),
+                None => t!("ui.cleaned").to_string(),
+            };
+            let presence = |size: Option<u64>| if size.is_some() { "✓" } else { "-" };
+
+            let row_style = if is_current {
+                Style::default().bg(Color::DarkGray)
+            } else if !in_range || file.actual_size.is_none() {
+                Style::default().fg(Color::DarkGray)
+            } else {
+                Style::default()
+            };
+
+            Row::new(vec![
+                Cell::from(checkbox).style(if is_selected {
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                }),
+                Cell::from(file.file_name.clone()),
+                Cell::from(time),
+                Cell::from(size).style(Style::default().fg(Color::Cyan)),
+                Cell::from(presence(file.ori_size)),
+                Cell::from(presence(file.thumb_size)),
+                if file.is_original() {
+                    Cell::from("★").style(Style::default().fg(Color::Yellow))
+                } else {
+                    Cell::from("")
+                },
+                // 数据库中只有不透明的 uid，没有昵称；完整 uid 显示在列表底部
+                Cell::from(truncate(&file.nt_uid, 12)),
+                Cell::from(file.media_type().description()),
+            ])
+            .style(row_style)
+        })
+        .collect();
+
+    let selected_count = stat
+        .files
+        .iter()
+        .filter(|file| app.is_file_selected(&stat.group_id, file))
+        .count();
+    let arrow = if view.descending { "↓" } else { "↑" };
+    let title = t!(
+        "ui.file_list_title",
+        truncate(&stat.group_name, 20),
+        selected_count,
+        total_items,
+        view.sort_by.description(),
+        arrow,
+        key_hint(app, Action::Back)
+    );
+    let mut block = Block::default().borders(Borders::ALL).title(title);
+    if let Some(file) = view.order.get(view.selected_index).map(|&idx| &stat.files[idx]) {
+        if !file.nt_uid.is_empty() {
+            block = block.title_bottom(t!("ui.file_sender_uid", file.nt_uid));
+        }
+    }
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Length(3),  // checkbox
+            Constraint::Min(12),    // 文件名
+            Constraint::Length(16), // 时间
+            Constraint::Length(10), // 大小
+            Constraint::Length(4),  // 原图
+            Constraint::Length(6),  // 缩略图
+            Constraint::Length(4),  // 原图画质
+            Constraint::Length(12), // 发送者 uid
+            Constraint::Length(4),  // 类型
+        ],
+    )
+    .header(header)
+    .block(block)
+    .column_spacing(1);
+
+    f.render_widget(table, area);
+}
+
 fn render_clean_options(f: &mut Frame, app: &App, area: Rect) {
     let selected_count = app.selected_count();
-    let deletable_size = app.selected_deletable_size();
+    let projection = app.reclaim_projection(false);
+    let ori_freed = if app.clean_scope.includes_ori() { projection.ori_local } else { 0 };
+    let thumb_freed = if app.clean_scope.includes_thumb() { projection.thumb_local } else { 0 };
     let total_size = app.selected_total_size();
+    let linked_size = app.selected_linked_size();
 
     let text = vec![
         Line::from(vec![Span::styled(
-            "清理选项",
+            t!("ui.clean_options"),
             Style::default()
                 .add_modifier(Modifier::BOLD)
                 .fg(Color::Yellow),
@@ -248,7 +918,7 @@ fn render_clean_options(f: &mut Frame, app: &App, area: Rect) {
         Line::from(""),
         Line::from(vec![
             Span::styled(
-                "已选择群组: ",
+                t!("ui.selected_groups"),
                 Style::default().add_modifier(Modifier::BOLD),
             ),
             Span::styled(selected_count.to_string(), Style::default().fg(Color::Cyan)),
@@ -256,54 +926,136 @@ fn render_clean_options(f: &mut Frame, app: &App, area: Rect) {
         Line::from(""),
         Line::from(vec![
             Span::styled(
-                "文件总大小: ",
+                t!("ui.total_size"),
                 Style::default().add_modifier(Modifier::BOLD),
             ),
             Span::styled(format_bytes(total_size), Style::default().fg(Color::Cyan)),
         ]),
+        local_linked_line(total_size, linked_size),
         Line::from(""),
         Line::from(vec![
-            Span::styled("预计释放: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(t!("ui.clean_scope"), Style::default().add_modifier(Modifier::BOLD)),
             Span::styled(
-                format_bytes(deletable_size),
+                app.clean_scope.description(),
+                Style::default().fg(Color::Yellow),
+            ),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(t!("ui.expected_free"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(
+                format_bytes(projection.freed(app.clean_scope)),
                 Style::default()
                     .fg(Color::Green)
                     .add_modifier(Modifier::BOLD),
             ),
         ]),
+        Line::from(vec![
+            Span::styled(t!("ui.projection_ori_short"), Style::default().fg(Color::DarkGray)),
+            Span::styled(format_bytes(ori_freed), Style::default().fg(Color::Gray)),
+            Span::styled(t!("ui.projection_thumb_short"), Style::default().fg(Color::DarkGray)),
+            Span::styled(format_bytes(thumb_freed), Style::default().fg(Color::Gray)),
+        ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("时间范围: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(t!("ui.time_range"), Style::default().add_modifier(Modifier::BOLD)),
             Span::styled(
                 app.time_range.description(),
                 Style::default().fg(Color::Yellow),
             ),
         ]),
+        original_filter_line(app),
+        file_rules_line(app),
+        policy_line(app),
         Line::from(""),
         Line::from("─".repeat(35)),
         Line::from(""),
         Line::from(vec![
-            Span::styled("[t] ", Style::default().fg(Color::Cyan)),
-            Span::raw("切换时间范围"),
+            Span::styled(format!("{} ", key_hint(app, Action::TimeRange)), Style::default().fg(Color::Cyan)),
+            Span::raw(t!("ui.cycle_time_range")),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{} ", key_hint(app, Action::OriginalFilter)), Style::default().fg(Color::Cyan)),
+            Span::raw(t!("ui.cycle_original_filter")),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{} ", key_hint(app, Action::GroupPolicy)), Style::default().fg(Color::Cyan)),
+            Span::raw(t!("ui.cycle_group_policy")),
+        ]),
+        Line::from(vec![
+            Span::styled(format!("{} ", key_hint(app, Action::CleanScope)), Style::default().fg(Color::Cyan)),
+            Span::raw(t!("ui.cycle_clean_scope")),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("[d] ", Style::default().fg(Color::Red)),
-            Span::raw("开始清理"),
+            Span::styled(format!("{} ", key_hint(app, Action::Clean)), Style::default().fg(Color::Red)),
+            Span::raw(t!("ui.start_clean")),
         ]),
     ];
 
     let paragraph = Paragraph::new(text)
-        .block(Block::default().borders(Borders::ALL).title(" 清理配置 "))
+        .block(Block::default().borders(Borders::ALL).title(t!("ui.clean_config")))
         .wrap(Wrap { trim: true });
 
     f.render_widget(paragraph, area);
 }
 
+/// 原图画质筛选，全部时用灰色显示
+fn original_filter_line(app: &App) -> Line<'static> {
+    let color = if app.original_filter == OriginalFilter::All {
+        Color::DarkGray
+    } else {
+        Color::Yellow
+    };
+    Line::from(vec![
+        Span::styled(t!("ui.original_filter"), Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(app.original_filter.description(), Style::default().fg(color)),
+    ])
+}
+
+/// 过滤器中设置的逐文件条件
+fn file_rules_line(app: &App) -> Line<'static> {
+    let (summary, color) = match app.filter.files.summary() {
+        Some(summary) => (summary, Color::Yellow),
+        None => (t!("ui.unlimited").to_string(), Color::DarkGray),
+    };
+    Line::from(vec![
+        Span::styled(t!("ui.file_rules"), Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(summary, Style::default().fg(color)),
+    ])
+}
+
+/// 每个群的保留策略
+fn policy_line(app: &App) -> Line<'static> {
+    let color = if app.group_policy == GroupPolicy::None {
+        Color::DarkGray
+    } else {
+        Color::Yellow
+    };
+    Line::from(vec![
+        Span::styled(t!("ui.group_policy"), Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(app.group_policy.description(), Style::default().fg(color)),
+    ])
+}
+
+/// 本地文件与符号链接（位于迁移目录）大小的分项
+fn local_linked_line(total_size: u64, linked_size: u64) -> Line<'static> {
+    Line::from(vec![
+        Span::styled(t!("ui.local"), Style::default().fg(Color::DarkGray)),
+        Span::styled(
+            format_bytes(total_size.saturating_sub(linked_size)),
+            Style::default().fg(Color::Gray),
+        ),
+        Span::styled(t!("ui.linked"), Style::default().fg(Color::DarkGray)),
+        Span::styled(format_bytes(linked_size), Style::default().fg(Color::Gray)),
+    ])
+}
+
 fn render_migrate_options(f: &mut Frame, app: &App, area: Rect) {
     let selected_count = app.selected_count();
     let selected_size = app.selected_total_size();
     let deletable_size = app.selected_deletable_size();
+    let linked_size = app.selected_linked_size();
 
     // 显示当前路径索引和总数
     let path_indicator = format!(
@@ -326,7 +1078,7 @@ fn render_migrate_options(f: &mut Frame, app: &App, area: Rect) {
 
     let text = vec![
         Line::from(vec![Span::styled(
-            "迁移选项",
+            t!("ui.migrate_options"),
             Style::default()
                 .add_modifier(Modifier::BOLD)
                 .fg(Color::Yellow),
@@ -334,7 +1086,7 @@ fn render_migrate_options(f: &mut Frame, app: &App, area: Rect) {
         Line::from(""),
         Line::from(vec![
             Span::styled(
-                "已选择群组: ",
+                t!("ui.selected_groups"),
                 Style::default().add_modifier(Modifier::BOLD),
             ),
             Span::styled(selected_count.to_string(), Style::default().fg(Color::Cyan)),
@@ -342,7 +1094,7 @@ fn render_migrate_options(f: &mut Frame, app: &App, area: Rect) {
         Line::from(""),
         Line::from(vec![
             Span::styled(
-                "文件总大小: ",
+                t!("ui.total_size"),
                 Style::default().add_modifier(Modifier::BOLD),
             ),
             Span::styled(
@@ -350,10 +1102,11 @@ fn render_migrate_options(f: &mut Frame, app: &App, area: Rect) {
                 Style::default().fg(Color::Cyan),
             ),
         ]),
+        local_linked_line(selected_size, linked_size),
         Line::from(""),
         Line::from(vec![
             Span::styled(
-                "范围内大小: ",
+                t!("ui.in_range_size"),
                 Style::default().add_modifier(Modifier::BOLD),
             ),
             Span::styled(
@@ -365,15 +1118,17 @@ fn render_migrate_options(f: &mut Frame, app: &App, area: Rect) {
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("时间范围: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(t!("ui.time_range"), Style::default().add_modifier(Modifier::BOLD)),
             Span::styled(
                 app.time_range.description(),
                 Style::default().fg(Color::Yellow),
             ),
         ]),
+        original_filter_line(app),
+        file_rules_line(app),
         Line::from(""),
         Line::from(vec![
-            Span::styled("目标路径: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(t!("ui.target_path"), Style::default().add_modifier(Modifier::BOLD)),
             Span::styled(path_indicator, Style::default().fg(Color::DarkGray)),
         ]),
         Line::from(vec![
@@ -384,30 +1139,43 @@ fn render_migrate_options(f: &mut Frame, app: &App, area: Rect) {
         Line::from("─".repeat(35)),
         Line::from(""),
         Line::from(vec![
-            Span::styled("[t] ", Style::default().fg(Color::Cyan)),
-            Span::raw("切换时间范围"),
+            Span::styled(format!("{} ", key_hint(app, Action::TimeRange)), Style::default().fg(Color::Cyan)),
+            Span::raw(t!("ui.cycle_time_range")),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("[←→/p] ", Style::default().fg(Color::Cyan)),
-            Span::raw("切换路径"),
+            Span::styled(
+                format!("[{}/{}] ", app.keymap.label(Action::PrevPath), app.keymap.label(Action::NextPath)),
+                Style::default().fg(Color::Cyan),
+            ),
+            Span::raw(t!("ui.switch_path")),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("{} ", key_hint(app, Action::Migrate)), Style::default().fg(Color::Green)),
+            Span::raw(t!("ui.start_migrate")),
+        ]),
+        Line::from(""),
+        Line::from(vec![
+            Span::styled(format!("{} ", key_hint(app, Action::Restore)), Style::default().fg(Color::Cyan)),
+            Span::raw(t!("ui.restore_to_qq")),
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("[m] ", Style::default().fg(Color::Green)),
-            Span::raw("开始迁移"),
+            Span::styled(format!("{} ", key_hint(app, Action::Doctor)), Style::default().fg(Color::Cyan)),
+            Span::raw(t!("ui.check_links")),
         ]),
     ];
 
     let paragraph = Paragraph::new(text)
-        .block(Block::default().borders(Borders::ALL).title(" 迁移配置 "))
+        .block(Block::default().borders(Borders::ALL).title(t!("ui.migrate_config")))
         .wrap(Wrap { trim: true });
 
     f.render_widget(paragraph, area);
 }
 
 fn render_top_groups(f: &mut Frame, app: &App, area: Rect) {
-    let headers = ["群组名称", "文件数(范围内)", "占用空间(范围内)"];
+    let headers = [t!("ui.col_group_name"), t!("ui.col_files_in_range"), t!("ui.col_size_in_range")];
     let header_cells = headers.iter().map(|h| {
         Cell::from(*h).style(
             Style::default()
@@ -446,65 +1214,241 @@ fn render_top_groups(f: &mut Frame, app: &App, area: Rect) {
     .block(
         Block::default()
             .borders(Borders::ALL)
-            .title(" Top 10 群组 "),
+            .title(t!("ui.top_groups")),
     );
 
     f.render_widget(table, area);
 }
 
-fn render_time_distribution(f: &mut Frame, app: &App, area: Rect) {
-    use chrono::{DateTime, Datelike, Utc};
-    use std::collections::HashMap;
+fn render_treemap(f: &mut Frame, app: &App, area: Rect) {
+    use crate::treemap::TileTarget;
+
+    let tiles = app.treemap_tiles();
+    let level = match (&app.treemap_group, app.treemap_year) {
+        (None, _) => t!("ui.treemap_all").to_string(),
+        (Some(group_id), year) => {
+            let name = app
+                .stats
+                .iter()
+                .find(|s| &s.group_id == group_id)
+                .map(|s| truncate(&s.group_name, 16))
+                .unwrap_or_else(|| group_id.clone());
+            match year {
+                Some(year) => format!("{} / {}", name, year),
+                None => name,
+            }
+        }
+    };
+    let title = t!(
+        "ui.treemap_title",
+        level,
+        app.keymap.label(Action::Open),
+        app.keymap.label(Action::Back),
+        app.keymap.label(Action::JumpToGroup)
+    );
+    let block = Block::default().borders(Borders::ALL).title(title);
 
-    let mut month_stats: HashMap<String, u64> = HashMap::new();
+    if tiles.is_empty() {
+        render_no_data(f, area, block);
+        return;
+    }
 
-    for stat in &app.stats {
-        for file in &stat.files {
-            if !app.time_range.should_delete(file.msg_time) {
-                continue;
-            }
-            if let Some(datetime) = DateTime::<Utc>::from_timestamp(file.msg_time, 0) {
-                let month_key = format!("{}-{:02}", datetime.year(), datetime.month());
-                let size = file.actual_size.unwrap_or(0);
-                *month_stats.entry(month_key).or_insert(0) += size;
-            }
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let sizes: Vec<u64> = tiles.iter().map(|tile| tile.size).collect();
+    let rects = crate::treemap::layout(&sizes, inner);
+    let cursor = app.treemap_cursor.min(tiles.len() - 1);
+    let palette = [Color::Blue, Color::Green, Color::Magenta, Color::Cyan, Color::Red, Color::Yellow];
+
+    for (index, (tile, rect)) in tiles.iter().zip(rects).enumerate() {
+        if rect.is_empty() {
+            continue;
+        }
+
+        let style = if index == cursor {
+            Style::default().fg(Color::Black).bg(Color::White).add_modifier(Modifier::BOLD)
+        } else if tile.target == TileTarget::Others {
+            Style::default().fg(Color::White).bg(Color::DarkGray)
+        } else {
+            Style::default().fg(Color::Black).bg(palette[index % palette.len()])
+        };
+
+        // 太窄的块放不下省略号，直接截断
+        let fit = |text: &str| match rect.width as usize {
+            width if width < 4 => text.chars().take(width).collect(),
+            width => truncate(text, width),
+        };
+        let mut lines = vec![Line::from(fit(&tile.label))];
+        if rect.height > 1 {
+            lines.push(Line::from(fit(&format_bytes(tile.size))));
         }
+        f.render_widget(Paragraph::new(lines).style(style), rect);
     }
+}
 
-    let mut month_vec: Vec<_> = month_stats.into_iter().collect();
-    month_vec.sort_by(|a, b| a.0.cmp(&b.0));
+fn render_time_distribution(f: &mut Frame, app: &App, area: Rect) {
+    let scope = if app.chart_selected_only && app.selected_count() > 0 {
+        t!("ui.chart_scope_selected", app.selected_count())
+    } else {
+        t!("ui.chart_scope_all").to_string()
+    };
+    let title = t!(
+        "ui.time_chart_title",
+        app.time_range.description(),
+        app.chart_metric.description(),
+        scope,
+        app.keymap.label(Action::ChartOlder),
+        app.keymap.label(Action::ChartNewer),
+        app.keymap.label(Action::ChartMetric),
+        app.keymap.label(Action::ChartSelected),
+        app.keymap.label(Action::ChartView)
+    );
+    let block = Block::default().borders(Borders::ALL).title(title);
+
+    match app.chart_view {
+        ChartView::Monthly => render_monthly_chart(f, app, area, block),
+        ChartView::Heatmap => render_heatmap(f, app, area, block),
+    }
+}
+
+fn render_no_data(f: &mut Frame, area: Rect, block: Block) {
+    let paragraph = Paragraph::new(t!("ui.no_data"))
+        .block(block)
+        .alignment(Alignment::Center);
+    f.render_widget(paragraph, area);
+}
+
+fn render_monthly_chart(f: &mut Frame, app: &App, area: Rect, block: Block) {
+    let months = app.monthly_activity();
 
     let bar_width = 8u16;
     let bar_gap = 1u16;
     let available_width = area.width.saturating_sub(2);
     let max_bars = (available_width / (bar_width + bar_gap)).max(1) as usize;
 
-    let skip_count = month_vec.len().saturating_sub(max_bars);
-    let month_vec: Vec<_> = month_vec.into_iter().skip(skip_count).collect();
+    // chart_scroll 从最新的月份往回数，和日志页的滚动方式一致
+    let end = months.len().saturating_sub(app.chart_scroll);
+    let start = end.saturating_sub(max_bars);
 
-    let data: Vec<(&str, u64)> = month_vec
+    let data: Vec<(&str, u64)> = months[start..end]
         .iter()
-        .map(|(month, size)| (month.as_str(), *size / 1024 / 1024))
+        .map(|(month, activity)| {
+            let value = match app.chart_metric {
+                ChartMetric::Bytes => activity.bytes / 1024 / 1024,
+                ChartMetric::Count => activity.count as u64,
+            };
+            (month.as_str(), value)
+        })
         .collect();
 
-    let title = format!(" 文件大小时间分布 (MB) - {} ", app.time_range.description());
+    if data.is_empty() {
+        render_no_data(f, area, block);
+        return;
+    }
+
+    let chart = BarChart::default()
+        .block(block)
+        .data(&data)
+        .bar_width(bar_width)
+        .bar_gap(bar_gap)
+        .bar_style(Style::default().fg(Color::Cyan))
+        .value_style(Style::default().fg(Color::White));
 
-    if !data.is_empty() {
-        let chart = BarChart::default()
-            .block(Block::default().borders(Borders::ALL).title(title))
-            .data(&data)
-            .bar_width(bar_width)
-            .bar_gap(bar_gap)
-            .bar_style(Style::default().fg(Color::Cyan))
-            .value_style(Style::default().fg(Color::White));
+    f.render_widget(chart, area);
+}
+
+/// 日历热力图：每列一周，每行一个星期几，颜色深浅表示当天的大小或数量
+fn render_heatmap(f: &mut Frame, app: &App, area: Rect, block: Block) {
+    use chrono::{Datelike, Days};
+
+    let days = app.daily_activity();
+    let (Some(&first), Some(&last)) = (days.keys().next(), days.keys().next_back()) else {
+        render_no_data(f, area, block);
+        return;
+    };
+
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    const LABEL_WIDTH: u16 = 4;
+    const CELL_WIDTH: u16 = 2;
+    let max_weeks = (inner.width.saturating_sub(LABEL_WIDTH) / CELL_WIDTH).max(1) as usize;
+
+    let monday = |date: NaiveDate| date - Days::new(date.weekday().num_days_from_monday() as u64);
+    let first_week = monday(first);
+    let total_weeks = ((monday(last) - first_week).num_days() / 7 + 1) as usize;
+
+    let end = total_weeks.saturating_sub(app.chart_scroll);
+    let start = end.saturating_sub(max_weeks);
+    let weeks: Vec<NaiveDate> = (start..end)
+        .map(|week| first_week + Days::new(week as u64 * 7))
+        .collect();
+
+    let max_value = days
+        .values()
+        .map(|activity| activity.value(app.chart_metric))
+        .max()
+        .unwrap_or(0)
+        .max(1);
+    let shades = [Color::DarkGray, Color::Blue, Color::Cyan, Color::Green, Color::Yellow];
+
+    // 第一行是月份，在每月第一周的位置标出
+    let mut month_line = vec![Span::raw(" ".repeat(LABEL_WIDTH as usize))];
+    let mut previous_month = None;
+    let mut skip = 0;
+    for week in &weeks {
+        if skip > 0 {
+            skip -= 1;
+            continue;
+        }
+        let month = (*week + Days::new(6)).month();
+        if previous_month != Some(month) {
+            previous_month = Some(month);
+            month_line.push(Span::styled(format!("{:<4}", format!("{:02}", month)), Style::default().fg(Color::DarkGray)));
+            skip = 1;
+        } else {
+            month_line.push(Span::raw("  "));
+        }
+    }
+
+    let mut lines = vec![Line::from(month_line)];
+    let weekday_labels = t!("ui.heatmap_weekdays");
+    for (weekday, label) in weekday_labels.split(',').enumerate().take(7) {
+        let mut spans = vec![Span::styled(
+            format!("{:<width$}", label, width = LABEL_WIDTH as usize - 1) + " ",
+            Style::default().fg(Color::DarkGray),
+        )];
+        for week in &weeks {
+            let date = *week + Days::new(weekday as u64);
+            let cell = if date < first || date > last {
+                Span::raw("  ")
+            } else {
+                let value = days.get(&date).map(|a| a.value(app.chart_metric)).unwrap_or(0);
+                let shade = if value == 0 {
+                    0
+                } else {
+                    1 + (value * (shades.len() as u64 - 2) / max_value) as usize
+                };
+                Span::styled("■ ", Style::default().fg(shades[shade.min(shades.len() - 1)]))
+            };
+            spans.push(cell);
+        }
+        lines.push(Line::from(spans));
+    }
 
-        f.render_widget(chart, area);
-    } else {
-        let paragraph = Paragraph::new("暂无数据")
-            .block(Block::default().borders(Borders::ALL).title(title))
-            .alignment(Alignment::Center);
-        f.render_widget(paragraph, area);
+    if let (Some(from), Some(to)) = (weeks.first(), weeks.last()) {
+        let max_text = match app.chart_metric {
+            ChartMetric::Bytes => format_bytes(max_value),
+            ChartMetric::Count => max_value.to_string(),
+        };
+        lines.push(Line::from(Span::styled(
+            t!("ui.heatmap_legend", from, *to + Days::new(6), max_text),
+            Style::default().fg(Color::DarkGray),
+        )));
     }
+
+    f.render_widget(Paragraph::new(lines), inner);
 }
 
 fn render_statistics_summary(f: &mut Frame, app: &App, area: Rect) {
@@ -529,13 +1473,16 @@ fn render_statistics_summary(f: &mut Frame, app: &App, area: Rect) {
     let text = vec![
         Line::from(vec![
             Span::styled(
-                "统计摘要",
+                t!("ui.summary"),
                 Style::default()
                     .add_modifier(Modifier::BOLD)
                     .fg(Color::Yellow),
             ),
             Span::raw("  "),
-            Span::styled("[t] 时间范围: ", Style::default().fg(Color::DarkGray)),
+            Span::styled(
+                t!("ui.time_range_hint", key_hint(app, Action::TimeRange)),
+                Style::default().fg(Color::DarkGray),
+            ),
             Span::styled(
                 app.time_range.description(),
                 Style::default().fg(Color::Yellow),
@@ -543,19 +1490,19 @@ fn render_statistics_summary(f: &mut Frame, app: &App, area: Rect) {
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("总群组数: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(t!("ui.total_groups"), Style::default().add_modifier(Modifier::BOLD)),
             Span::styled(total_groups.to_string(), Style::default().fg(Color::Cyan)),
             Span::raw("  "),
-            Span::styled("总文件数: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(t!("ui.total_files"), Style::default().add_modifier(Modifier::BOLD)),
             Span::styled(total_files.to_string(), Style::default().fg(Color::Cyan)),
             Span::raw("  "),
-            Span::styled("总大小: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(t!("ui.total_size_short"), Style::default().add_modifier(Modifier::BOLD)),
             Span::styled(format_bytes(total_size), Style::default().fg(Color::Cyan)),
         ]),
         Line::from(""),
         Line::from(vec![
             Span::styled(
-                "范围内文件: ",
+                t!("ui.files_in_range"),
                 Style::default().add_modifier(Modifier::BOLD),
             ),
             Span::styled(
@@ -566,7 +1513,7 @@ fn render_statistics_summary(f: &mut Frame, app: &App, area: Rect) {
             ),
             Span::raw("  "),
             Span::styled(
-                "范围内大小: ",
+                t!("ui.in_range_size"),
                 Style::default().add_modifier(Modifier::BOLD),
             ),
             Span::styled(
@@ -578,11 +1525,11 @@ fn render_statistics_summary(f: &mut Frame, app: &App, area: Rect) {
         ]),
         Line::from(""),
         Line::from(vec![
-            Span::styled("存在文件: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(t!("ui.existing_files"), Style::default().add_modifier(Modifier::BOLD)),
             Span::styled(total_exist.to_string(), Style::default().fg(Color::Green)),
             Span::raw("  "),
             Span::styled(
-                "缺失(已清理)文件: ",
+                t!("ui.missing_files"),
                 Style::default().add_modifier(Modifier::BOLD),
             ),
             Span::styled(total_missing.to_string(), Style::default().fg(Color::Red)),
@@ -590,131 +1537,195 @@ fn render_statistics_summary(f: &mut Frame, app: &App, area: Rect) {
     ];
 
     let paragraph = Paragraph::new(text)
-        .block(Block::default().borders(Borders::ALL).title(" 总体统计 "))
+        .block(Block::default().borders(Borders::ALL).title(t!("ui.overall")))
         .wrap(Wrap { trim: true });
 
     f.render_widget(paragraph, area);
 }
 
-fn render_help_dialog(f: &mut Frame) {
-    let area = centered_rect(60, 70, f.area());
+/// 按清理范围列出去重后实际释放的空间，没有选择群组时按全部群组计算
+fn render_reclaim_projection(f: &mut Frame, app: &App, area: Rect) {
+    let projection = app.reclaim_projection(true);
+    let target = match app.selected_count() {
+        0 => t!("ui.projection_all").to_string(),
+        count => t!("ui.projection_selected", count),
+    };
 
-    let text = vec![
-        Line::from(vec![Span::styled(
-            "快捷键说明",
+    let headers = [
+        t!("ui.projection_scope"),
+        t!("ui.projection_ori"),
+        t!("ui.projection_thumb"),
+        t!("ui.projection_freed"),
+        t!("ui.projection_linked"),
+        t!("ui.projection_shared"),
+    ];
+    let header = Row::new(headers.iter().map(|h| {
+        Cell::from(*h).style(
             Style::default()
-                .add_modifier(Modifier::BOLD)
-                .fg(Color::Yellow),
-        )]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "全局操作:",
-            Style::default().add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("  [q] ", Style::default().fg(Color::Cyan)),
-            Span::raw("退出程序"),
-        ]),
-        Line::from(vec![
-            Span::styled("  [?/h] ", Style::default().fg(Color::Cyan)),
-            Span::raw("显示/隐藏帮助"),
-        ]),
-        Line::from(vec![
-            Span::styled("  [Tab] ", Style::default().fg(Color::Cyan)),
-            Span::raw("切换标签页"),
-        ]),
-        Line::from(vec![
-            Span::styled("  [1-3] ", Style::default().fg(Color::Cyan)),
-            Span::raw("快速跳转到对应标签页"),
-        ]),
-        Line::from(vec![
-            Span::styled("  [t] ", Style::default().fg(Color::Cyan)),
-            Span::raw("切换时间范围"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "群组操作:",
-            Style::default().add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("  [↑↓/jk] ", Style::default().fg(Color::Cyan)),
-            Span::raw("上下移动"),
-        ]),
-        Line::from(vec![
-            Span::styled("  [Space] ", Style::default().fg(Color::Cyan)),
-            Span::raw("选择/取消选择当前群组"),
-        ]),
-        Line::from(vec![
-            Span::styled("  [a] ", Style::default().fg(Color::Green)),
-            Span::raw("全选当前过滤的群组"),
-        ]),
-        Line::from(vec![
-            Span::styled("  [A] ", Style::default().fg(Color::Red)),
-            Span::raw("取消所有选择"),
-        ]),
-        Line::from(vec![
-            Span::styled("  [s] ", Style::default().fg(Color::Cyan)),
-            Span::raw("切换排序方式"),
-        ]),
-        Line::from(vec![
-            Span::styled("  [f] ", Style::default().fg(Color::Cyan)),
-            Span::raw("打开过滤器（隐藏空群组、不活跃群组）"),
-        ]),
-        Line::from(""),
-        Line::from(vec![Span::styled(
-            "清理操作:",
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
+        )
+    }));
+
+    let part = |included: bool, size: u64| {
+        if included {
+            Cell::from(format_bytes(size))
+        } else {
+            Cell::from("-").style(Style::default().fg(Color::DarkGray))
+        }
+    };
+    let rows: Vec<Row> = CleanScope::ALL
+        .iter()
+        .map(|scope| {
+            let marker = if *scope == app.clean_scope { "▶ " } else { "  " };
+            Row::new(vec![
+                Cell::from(format!("{}{}", marker, scope.description())),
+                part(scope.includes_ori(), projection.ori_local),
+                part(scope.includes_thumb(), projection.thumb_local),
+                Cell::from(format_bytes(projection.freed(*scope))).style(
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD),
+                ),
+                Cell::from(format_bytes(projection.linked(*scope)))
+                    .style(Style::default().fg(Color::Gray)),
+                Cell::from(format_bytes(projection.shared(*scope)))
+                    .style(Style::default().fg(Color::Magenta)),
+            ])
+        })
+        .collect();
+
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(match app.group_policy {
+            GroupPolicy::None => t!("ui.projection_title", target, app.time_range.description()),
+            policy => t!(
+                "ui.projection_title_policy",
+                target,
+                app.time_range.description(),
+                policy.description()
+            ),
+        });
+    let inner = block.inner(area);
+    f.render_widget(block, area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Vertical)
+        .constraints([Constraint::Length(5), Constraint::Min(0)])
+        .split(inner);
+
+    let table = Table::new(
+        rows,
+        [
+            Constraint::Percentage(22),
+            Constraint::Percentage(15),
+            Constraint::Percentage(15),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+            Constraint::Percentage(16),
+        ],
+    )
+    .header(header);
+    f.render_widget(table, chunks[0]);
+
+    let notes = vec![
+        Line::from(Span::styled(
+            t!(
+                "ui.projection_dedup",
+                projection.files,
+                format_bytes(projection.dedup(CleanScope::Both))
+            ),
+            Style::default().fg(Color::DarkGray),
+        )),
+        Line::from(Span::styled(
+            t!("ui.projection_note"),
+            Style::default().fg(Color::DarkGray),
+        )),
+    ];
+    f.render_widget(Paragraph::new(notes).wrap(Wrap { trim: true }), chunks[1]);
+}
+
+fn render_help_dialog(f: &mut Frame, app: &App) {
+    let area = centered_rect(80, 85, f.area());
+
+    // 按键说明由当前快捷键配置生成，分成左右两栏
+    let mut columns: [Vec<Line>; 2] = [Vec::new(), Vec::new()];
+    for (idx, context) in KeyContext::ALL.iter().enumerate() {
+        let column = &mut columns[if idx < KeyContext::ALL.len() / 2 { 0 } else { 1 }];
+
+        column.push(Line::from(vec![Span::styled(
+            format!("{}:", context.title()),
             Style::default().add_modifier(Modifier::BOLD),
-        )]),
-        Line::from(vec![
-            Span::styled("  [d] ", Style::default().fg(Color::Red)),
-            Span::raw("执行清理操作"),
-        ]),
-        Line::from(""),
+        )]));
+        for action in Action::ALL.iter().filter(|a| a.contexts()[0] == *context) {
+            column.push(Line::from(vec![
+                Span::styled(
+                    format!("  [{}] ", app.keymap.label(*action)),
+                    Style::default().fg(Color::Cyan),
+                ),
+                Span::raw(action.description()),
+            ]));
+        }
+        column.push(Line::from(""));
+    }
+
+    columns[1].extend([
         Line::from(vec![Span::styled(
-            "迁移操作:",
+            t!("ui.mouse"),
             Style::default().add_modifier(Modifier::BOLD),
         )]),
         Line::from(vec![
-            Span::styled("  [←→/p] ", Style::default().fg(Color::Cyan)),
-            Span::raw("切换迁移路径"),
+            Span::styled(t!("ui.mouse_wheel"), Style::default().fg(Color::Cyan)),
+            Span::raw(t!("ui.mouse_scroll")),
         ]),
         Line::from(vec![
-            Span::styled("  [m] ", Style::default().fg(Color::Green)),
-            Span::raw("执行迁移操作（确认时可选择是否保留原文件）"),
+            Span::styled(t!("ui.mouse_click"), Style::default().fg(Color::Cyan)),
+            Span::raw(t!("ui.mouse_click_desc")),
         ]),
-    ];
+    ]);
 
-    let paragraph = Paragraph::new(text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" 帮助 (按 ? 或 ESC 关闭) ")
-                .style(Style::default().bg(Color::Black)),
-        )
-        .wrap(Wrap { trim: true });
+    let block = Block::default()
+        .borders(Borders::ALL)
+        .title(t!(
+            "ui.help_title",
+            app.keymap.label(Action::Help)
+        ))
+        .style(Style::default().bg(Color::Black));
+    let inner = block.inner(area);
+
+    let chunks = Layout::default()
+        .direction(Direction::Horizontal)
+        .constraints([Constraint::Percentage(50), Constraint::Percentage(50)])
+        .split(inner);
 
     f.render_widget(Clear, area);
-    f.render_widget(paragraph, area);
+    f.render_widget(block, area);
+    for (column, chunk) in columns.into_iter().zip(chunks.iter()) {
+        f.render_widget(Paragraph::new(column).wrap(Wrap { trim: true }), *chunk);
+    }
 }
 
 fn render_confirm_dialog(f: &mut Frame, app: &App) {
-    let area = centered_rect(70, 40, f.area());
+    let area = confirm_dialog_area(f.area());
 
     let action_name = match app.confirm_action {
-        Some(crate::app::ConfirmAction::Clean) => "清理",
-        Some(crate::app::ConfirmAction::Migrate) => "迁移",
-        None => "操作",
+        Some(crate::app::ConfirmAction::Clean) => t!("ui.confirm_clean"),
+        Some(crate::app::ConfirmAction::Migrate) => t!("ui.confirm_migrate"),
+        Some(crate::app::ConfirmAction::Restore) => t!("ui.confirm_restore"),
+        None => t!("ui.confirm_other"),
     };
 
     let selected_count = app.selected_count();
     let selected_size = format_bytes(app.selected_total_size());
+    let in_range_size = format_bytes(app.selected_deletable_size());
 
     let is_migrate = matches!(app.confirm_action, Some(crate::app::ConfirmAction::Migrate));
+    let is_restore = matches!(app.confirm_action, Some(crate::app::ConfirmAction::Restore));
 
     let mut text = vec![
         Line::from(""),
         Line::from(vec![Span::styled(
-            format!("确认{}操作？", action_name),
+            action_name.to_string(),
             Style::default()
                 .add_modifier(Modifier::BOLD)
                 .fg(Color::Yellow),
@@ -723,28 +1734,44 @@ fn render_confirm_dialog(f: &mut Frame, app: &App) {
     ];
 
     if is_migrate {
-        text.push(Line::from(vec![Span::raw(format!(
-            "将迁移 {} 个群组",
+        text.push(Line::from(vec![Span::raw(t!(
+            "ui.will_migrate",
+            selected_count
+        ))]));
+        text.push(Line::from(vec![Span::raw(t!(
+            "ui.migrate_size",
+            in_range_size,
+            app.time_range.description()
+        ))]));
+    } else if is_restore {
+        text.push(Line::from(vec![Span::raw(t!(
+            "ui.will_restore",
             selected_count
         ))]));
         text.push(Line::from(vec![Span::raw(format!(
-            "迁移大小: {}",
-            selected_size
+            "{}{}",
+            t!("ui.time_range"),
+            app.time_range.description()
         ))]));
     } else {
-        text.push(Line::from(vec![Span::raw(format!(
-            "将影响 {} 个群组",
+        text.push(Line::from(vec![Span::raw(t!(
+            "ui.will_affect",
             selected_count
         ))]));
-        text.push(Line::from(vec![Span::raw(format!(
-            "总计: {}",
+        text.push(Line::from(vec![Span::raw(t!(
+            "ui.total",
             selected_size
         ))]));
+        text.push(Line::from(vec![Span::raw(t!(
+            "ui.confirm_clean_scope",
+            app.clean_scope.description(),
+            format_bytes(app.reclaim_projection(false).freed(app.clean_scope))
+        ))]));
     }
 
     text.push(Line::from(""));
 
-    if is_migrate {
+    if is_migrate || is_restore {
         // 显示目标路径
         let path_display = app.migrate_target_path.display().to_string();
         let max_path_len = 50;
@@ -763,7 +1790,7 @@ fn render_confirm_dialog(f: &mut Frame, app: &App) {
         );
 
         text.push(Line::from(vec![
-            Span::styled("目标路径: ", Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(t!("ui.target_path"), Style::default().add_modifier(Modifier::BOLD)),
             Span::styled(path_indicator, Style::default().fg(Color::DarkGray)),
         ]));
         text.push(Line::from(vec![
@@ -771,165 +1798,268 @@ fn render_confirm_dialog(f: &mut Frame, app: &App) {
             Span::styled(truncated_path, Style::default().fg(Color::Cyan)),
         ]));
         text.push(Line::from(vec![Span::styled(
-            "  (←→/p 切换)",
+            t!(
+                "ui.switch_path_hint",
+                app.keymap.label(Action::PrevPath),
+                app.keymap.label(Action::NextPath)
+            ),
             Style::default().fg(Color::DarkGray),
         )]));
         text.push(Line::from(""));
+    }
 
-        let checkbox = if app.temp_migrate_keep_original {
-            "[x]"
-        } else {
-            "[ ]"
-        };
-        let checkbox_style = if app.temp_migrate_keep_original {
-            Style::default()
+    if is_restore {
+        text.push(Line::from(vec![Span::styled(
+            t!("ui.restore_note"),
+            Style::default().fg(Color::DarkGray),
+        )]));
+        text.push(Line::from(""));
+    } else if is_migrate {
+        let mode = app.temp_migrate_mode;
+        let mode_style = match mode {
+            crate::app::MigrateMode::Copy => Style::default()
                 .fg(Color::Green)
-                .add_modifier(Modifier::BOLD)
-        } else {
-            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+                .add_modifier(Modifier::BOLD),
+            crate::app::MigrateMode::Move => {
+                Style::default().fg(Color::Red).add_modifier(Modifier::BOLD)
+            }
+            crate::app::MigrateMode::Link => Style::default()
+                .fg(Color::Yellow)
+                .add_modifier(Modifier::BOLD),
         };
 
         text.push(Line::from(vec![
-            Span::styled(checkbox, checkbox_style),
-            Span::raw(" "),
-            Span::styled("保留原文件", Style::default().add_modifier(Modifier::BOLD)),
-            Span::styled(" (空格切换)", Style::default().fg(Color::DarkGray)),
+            Span::styled(t!("ui.migrate_mode"), Style::default().add_modifier(Modifier::BOLD)),
+            Span::styled(mode.description(), mode_style),
+            Span::styled(
+                t!("ui.toggle_hint", app.keymap.label(Action::Toggle)),
+                Style::default().fg(Color::DarkGray),
+            ),
         ]));
+        if mode == crate::app::MigrateMode::Link {
+            text.push(Line::from(vec![Span::styled(
+                t!("ui.link_warning", key_hint(app, Action::Doctor)),
+                Style::default().fg(Color::DarkGray),
+            )]));
+        }
         text.push(Line::from(""));
     } else {
         text.push(Line::from(vec![Span::styled(
-            "此操作不可恢复！",
+            t!("ui.irreversible"),
             Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
         )]));
         text.push(Line::from(""));
     }
 
-    text.push(Line::from(""));
-    text.push(Line::from(vec![
-        Span::styled("[Y] ", Style::default().fg(Color::Green)),
-        Span::raw("确认  "),
-        Span::styled("[N/ESC] ", Style::default().fg(Color::Red)),
-        Span::raw("取消"),
-    ]));
+    if !app.preflight_issues.is_empty() {
+        text.push(Line::from(vec![Span::styled(
+            t!("ui.preflight_blocked"),
+            Style::default().fg(Color::Red).add_modifier(Modifier::BOLD),
+        )]));
+        for issue in &app.preflight_issues {
+            text.push(Line::from(vec![Span::styled(
+                format!("✗ {}", issue),
+                Style::default().fg(Color::Red),
+            )]));
+        }
+    }
 
     let paragraph = Paragraph::new(text)
         .block(
             Block::default()
                 .borders(Borders::ALL)
-                .title(" 确认操作 ")
+                .title(t!("ui.confirm_title"))
                 .style(Style::default().bg(Color::Black)),
         )
         .alignment(Alignment::Center);
 
     f.render_widget(Clear, area);
     f.render_widget(paragraph, area);
+    render_dialog_buttons(
+        f,
+        area,
+        &confirm_button_labels(app),
+        &[
+            Style::default().fg(Color::Green).bg(Color::Black),
+            Style::default().fg(Color::Red).bg(Color::Black),
+        ],
+    );
 }
 
 fn render_filter_dialog(f: &mut Frame, app: &App) {
-    let area = centered_rect(80, 50, f.area());
+    let area = filter_dialog_area(f.area());
     let inner_width = area.width.saturating_sub(4) as usize;
+    let (text, _) = filter_dialog_lines(app, inner_width);
+
+    let paragraph = Paragraph::new(text)
+        .block(
+            Block::default()
+                .borders(Borders::ALL)
+                .title(t!("ui.filter_title"))
+                .style(Style::default().bg(Color::Black)),
+        )
+        .wrap(Wrap { trim: true });
+
+    f.render_widget(Clear, area);
+    f.render_widget(paragraph, area);
+    render_dialog_buttons(
+        f,
+        area,
+        &filter_button_labels(app),
+        &[
+            Style::default()
+                .fg(Color::Green)
+                .bg(Color::Black)
+                .add_modifier(Modifier::BOLD),
+            Style::default().fg(Color::Red).bg(Color::Black),
+        ],
+    );
+}
 
+/// 过滤器对话框的内容，以及每个选项所在的行号（供鼠标点击定位）
+fn filter_dialog_lines(app: &App, inner_width: usize) -> (Vec<Line<'static>>, Vec<usize>) {
+    let mut option_lines = Vec::new();
     let mut text = vec![
         Line::from(vec![Span::styled(
-            "过滤器设置",
+            t!("ui.filter_heading"),
             Style::default()
                 .add_modifier(Modifier::BOLD)
                 .fg(Color::Yellow),
         )]),
         Line::from(""),
-        Line::from(vec![
-            Span::styled("使用 ", Style::default().fg(Color::DarkGray)),
-            Span::styled("↑↓/jk", Style::default().fg(Color::Cyan)),
-            Span::styled(" 导航, ", Style::default().fg(Color::DarkGray)),
-            Span::styled("空格/回车", Style::default().fg(Color::Cyan)),
-            Span::styled(" 切换", Style::default().fg(Color::DarkGray)),
-        ]),
+        Line::from(vec![Span::styled(
+            t!(
+                "ui.filter_hint",
+                app.keymap.label(Action::Up),
+                app.keymap.label(Action::Down),
+                app.keymap.label(Action::Toggle)
+            ),
+            Style::default().fg(Color::DarkGray),
+        )]),
         Line::from(""),
         Line::from("─".repeat(inner_width)),
         Line::from(""),
     ];
 
-    let cursor_0 = if app.filter_cursor == 0 { "► " } else { "  " };
-    let checkbox_0 = if app.temp_filter.hide_empty {
-        "[x]"
-    } else {
-        "[ ]"
-    };
-    text.push(Line::from(vec![
-        Span::styled(cursor_0, Style::default().fg(Color::Yellow)),
-        Span::styled(
-            checkbox_0,
-            if app.temp_filter.hide_empty {
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::DarkGray)
+    for (index, option) in FilterOption::ALL.iter().enumerate() {
+        let focused = app.filter_cursor == index;
+        let editing = focused && app.filter_input.is_some();
+        let filter = &app.temp_filter;
+
+        let (enabled, label) = match option {
+            FilterOption::HideEmpty => (filter.hide_empty, t!("ui.hide_empty").to_string()),
+            FilterOption::Activity => match filter.activity {
+                ActivityFilter::All => (false, t!("ui.activity_off").to_string()),
+                ActivityFilter::Active(days) => (true, t!("ui.activity_active", days)),
+                ActivityFilter::Inactive(days) => (true, t!("ui.activity_inactive", days)),
             },
-        ),
-        Span::raw(" "),
-        Span::styled(
-            "隐藏无图片群组 (exist_count = 0)",
-            if app.filter_cursor == 0 {
-                Style::default().add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
+            FilterOption::MinSize => match filter.min_size {
+                0 => (false, t!("ui.min_size", t!("ui.unlimited"))),
+                size => (true, t!("ui.min_size", format_bytes(size))),
             },
-        ),
-    ]));
-    text.push(Line::from(""));
-
-    let cursor_1 = if app.filter_cursor == 1 { "► " } else { "  " };
-    let (activity_checkbox, activity_text) = match app.temp_filter.activity {
-        crate::app::ActivityFilter::All => ("[ ]", "活跃度过滤: 关闭"),
-        crate::app::ActivityFilter::Active(days) => (
-            "[x]",
-            match days {
-                7 => "活跃度过滤: 活跃(7天内)",
-                30 => "活跃度过滤: 活跃(30天内)",
-                90 => "活跃度过滤: 活跃(90天内)",
-                _ => "活跃度过滤: 活跃",
+            FilterOption::MinFileCount => match filter.min_file_count {
+                0 => (false, t!("ui.min_file_count", t!("ui.unlimited"))),
+                count => (true, t!("ui.min_file_count", count)),
             },
-        ),
-        crate::app::ActivityFilter::Inactive(days) => (
-            "[x]",
-            match days {
-                7 => "活跃度过滤: 不活跃(7天前)",
-                30 => "活跃度过滤: 不活跃(30天前)",
-                90 => "活跃度过滤: 不活跃(90天前)",
-                _ => "活跃度过滤: 不活跃",
+            FilterOption::NamePattern => match filter.name_pattern {
+                None => (false, t!("ui.name_pattern", t!("ui.unlimited"))),
+                Some(ref pattern) => (true, t!("ui.name_pattern", pattern.0.as_str())),
             },
-        ),
-    };
-    text.push(Line::from(vec![
-        Span::styled(cursor_1, Style::default().fg(Color::Yellow)),
-        Span::styled(
-            activity_checkbox,
-            if !matches!(app.temp_filter.activity, crate::app::ActivityFilter::All) {
-                Style::default()
-                    .fg(Color::Green)
-                    .add_modifier(Modifier::BOLD)
-            } else {
-                Style::default().fg(Color::DarkGray)
+            FilterOption::Quit => match filter.quit {
+                QuitFilter::All => (false, t!("ui.quit_filter", t!("ui.quit_all"))),
+                QuitFilter::Quit => (true, t!("ui.quit_filter", t!("ui.quit_only"))),
+                QuitFilter::Member => (true, t!("ui.quit_filter", t!("ui.member_only"))),
             },
-        ),
-        Span::raw(" "),
-        Span::styled(
-            activity_text,
-            if app.filter_cursor == 1 {
-                Style::default().add_modifier(Modifier::BOLD)
-            } else {
-                Style::default()
+            FilterOption::MediaType => match filter.media_type {
+                None => (false, t!("ui.media_filter", t!("ui.unlimited"))),
+                Some(media_type) => (true, t!("ui.media_filter", media_type.description())),
             },
-        ),
-    ]));
-    text.push(Line::from(vec![
-        Span::raw("    "),
-        Span::styled(
-            "(关闭 → 活跃7/30/90天 → 不活跃7/30/90天 → 关闭)",
-            Style::default().fg(Color::DarkGray),
-        ),
-    ]));
+            FilterOption::FileMinSize => match filter.files.min_size {
+                0 => (false, t!("ui.file_min_size", t!("ui.unlimited"))),
+                size => (true, t!("ui.file_min_size", format_bytes(size))),
+            },
+            FilterOption::FileMaxSize => match filter.files.max_size {
+                None => (false, t!("ui.file_max_size", t!("ui.unlimited"))),
+                Some(size) => (true, t!("ui.file_max_size", format_bytes(size))),
+            },
+            FilterOption::Extensions => match filter.files.extensions {
+                None => (false, t!("ui.extensions", t!("ui.unlimited"))),
+                Some(ref extensions) => (true, t!("ui.extensions", extensions.join(", "))),
+            },
+            FilterOption::Animation => (
+                filter.files.animation != AnimationFilter::All,
+                t!("ui.animation_filter", filter.files.animation.description()),
+            ),
+        };
+
+        // 编辑时把标签中的当前值替换为输入内容
+        let label = match (editing, option) {
+            (true, FilterOption::MinSize) => t!("ui.min_size", input_text(app)),
+            (true, FilterOption::MinFileCount) => t!("ui.min_file_count", input_text(app)),
+            (true, FilterOption::NamePattern) => t!("ui.name_pattern", input_text(app)),
+            (true, FilterOption::FileMinSize) => t!("ui.file_min_size", input_text(app)),
+            (true, FilterOption::FileMaxSize) => t!("ui.file_max_size", input_text(app)),
+            (true, FilterOption::Extensions) => t!("ui.extensions", input_text(app)),
+            _ => label,
+        };
+
+        // 逐文件条件单独成组，放在群组条件之后
+        if *option == FilterOption::FileMinSize {
+            text.push(Line::from(""));
+            text.push(Line::from(vec![Span::styled(
+                t!("ui.file_rules_heading"),
+                Style::default().fg(Color::Yellow),
+            )]));
+        }
+
+        option_lines.push(text.len());
+        text.push(Line::from(vec![
+            Span::styled(if focused { "► " } else { "  " }, Style::default().fg(Color::Yellow)),
+            Span::styled(
+                if enabled { "[x]" } else { "[ ]" },
+                if enabled {
+                    Style::default()
+                        .fg(Color::Green)
+                        .add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default().fg(Color::DarkGray)
+                },
+            ),
+            Span::raw(" "),
+            Span::styled(
+                label,
+                if editing {
+                    Style::default().fg(Color::Yellow).add_modifier(Modifier::BOLD)
+                } else if focused {
+                    Style::default().add_modifier(Modifier::BOLD)
+                } else {
+                    Style::default()
+                },
+            ),
+        ]));
+
+        let hint = match option {
+            FilterOption::Activity => Some(t!("ui.activity_cycle").to_string()),
+            _ if editing => Some(match app.filter_input_error {
+                Some(ref error) => error.clone(),
+                None => t!("ui.filter_input_hint").to_string(),
+            }),
+            FilterOption::MinSize if focused => Some(t!("ui.min_size_hint").to_string()),
+            FilterOption::NamePattern if focused => Some(t!("ui.name_pattern_hint").to_string()),
+            FilterOption::FileMinSize | FilterOption::FileMaxSize if focused => {
+                Some(t!("ui.min_size_hint").to_string())
+            }
+            FilterOption::Extensions if focused => Some(t!("ui.extensions_hint").to_string()),
+            _ => None,
+        };
+        if let Some(hint) = hint {
+            let color = if editing && app.filter_input_error.is_some() { Color::Red } else { Color::DarkGray };
+            text.push(Line::from(vec![
+                Span::raw("    "),
+                Span::styled(hint, Style::default().fg(color)),
+            ]));
+        }
+    }
     text.push(Line::from(""));
 
     text.push(Line::from("─".repeat(inner_width)));
@@ -939,64 +2069,22 @@ fn render_filter_dialog(f: &mut Frame, app: &App) {
     let would_filter = app
         .stats
         .iter()
-        .filter(|stat| {
-            if app.temp_filter.hide_empty && stat.exist_count == 0 {
-                return false;
-            }
-            match app.temp_filter.activity {
-                crate::app::ActivityFilter::All => {}
-                crate::app::ActivityFilter::Active(days) => {
-                    let cutoff = now - (days * 86400);
-                    let latest_time = stat.files.iter().map(|f| f.msg_time).max().unwrap_or(0);
-                    if latest_time < cutoff {
-                        return false;
-                    }
-                }
-                crate::app::ActivityFilter::Inactive(days) => {
-                    let cutoff = now - (days * 86400);
-                    let latest_time = stat.files.iter().map(|f| f.msg_time).max().unwrap_or(0);
-                    if latest_time >= cutoff {
-                        return false;
-                    }
-                }
-            }
-            true
-        })
+        .filter(|stat| app.temp_filter.matches(stat, now))
         .count();
 
     text.push(Line::from(vec![
-        Span::styled("预览: ", Style::default().add_modifier(Modifier::BOLD)),
+        Span::styled(t!("ui.filter_preview"), Style::default().add_modifier(Modifier::BOLD)),
         Span::styled(
-            format!("将显示 {} / {} 个群组", would_filter, app.stats.len()),
+            t!("ui.would_show", would_filter, app.stats.len()),
             Style::default().fg(Color::Cyan),
         ),
     ]));
-    text.push(Line::from(""));
-    text.push(Line::from(""));
-
-    text.push(Line::from(vec![
-        Span::styled(
-            "[a] ",
-            Style::default()
-                .fg(Color::Green)
-                .add_modifier(Modifier::BOLD),
-        ),
-        Span::raw("应用 (Apply)   "),
-        Span::styled("[c/ESC] ", Style::default().fg(Color::Red)),
-        Span::raw("取消 (Cancel)"),
-    ]));
 
-    let paragraph = Paragraph::new(text)
-        .block(
-            Block::default()
-                .borders(Borders::ALL)
-                .title(" 过滤器设置 ")
-                .style(Style::default().bg(Color::Black)),
-        )
-        .wrap(Wrap { trim: true });
+    (text, option_lines)
+}
 
-    f.render_widget(Clear, area);
-    f.render_widget(paragraph, area);
+fn input_text(app: &App) -> String {
+    format!("{}▏", app.filter_input.as_deref().unwrap_or(""))
 }
 
 fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
@@ -1019,6 +2107,36 @@ fn centered_rect(percent_x: u16, percent_y: u16, r: Rect) -> Rect {
         .split(popup_layout[1])[1]
 }
 
+/// 按模糊匹配结果高亮文本中命中的字符
+fn highlight_matches(text: &str, query: &str, base: Style) -> Vec<Span<'static>> {
+    let positions = if query.is_empty() {
+        Vec::new()
+    } else {
+        fuzzy_match(query, text).map(|m| m.positions).unwrap_or_default()
+    };
+
+    let highlight = base.fg(Color::Magenta).add_modifier(Modifier::UNDERLINED);
+    let mut spans = Vec::new();
+    let mut current = String::new();
+    let mut current_hit = false;
+
+    for (idx, c) in text.chars().enumerate() {
+        let hit = positions.contains(&idx);
+        if hit != current_hit && !current.is_empty() {
+            let style = if current_hit { highlight } else { base };
+            spans.push(Span::styled(std::mem::take(&mut current), style));
+        }
+        current_hit = hit;
+        current.push(c);
+    }
+    if !current.is_empty() {
+        let style = if current_hit { highlight } else { base };
+        spans.push(Span::styled(current, style));
+    }
+
+    spans
+}
+
 fn truncate(s: &str, max_len: usize) -> String {
     if s.chars().count() <= max_len {
         s.to_string()